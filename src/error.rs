@@ -1,8 +1,9 @@
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use strum::Display;
 use thiserror::Error;
 
-use crate::LineResponseHeader;
+use crate::{LineResponseHeader, is_standard_retry};
 
 // https://developers.line.biz/ja/reference/line-login/index.html.md
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -31,6 +32,217 @@ pub struct ErrorDetail {
     pub property: String,
 }
 
+/// [`QuotaGuard`](crate::messaging_api::quota_guard::QuotaGuard) が送信前に拒否したときの内訳。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    /// 今回の送信で消費する見込みのメッセージ数
+    pub cost: u64,
+    /// 残りのメッセージ数(上限 - 使用数 - キャッシュ後に送った数)
+    pub remaining: i64,
+    pub limit: i64,
+}
+
+/// [`ValidationError`] が満たさなかった制約。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Constraint {
+    /// 空文字でないこと
+    NotEmpty,
+    /// 件数(文字列なら文字数)が `min..=max`
+    Length { min: usize, max: usize },
+    /// 値が `min..=max`(`step` があればその倍数)
+    #[serde(rename_all = "camelCase")]
+    Range {
+        min: i64,
+        max: i64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        step: Option<i64>,
+    },
+    /// `other` と同じか、それより前
+    Before { other: String },
+}
+
+impl std::fmt::Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constraint::NotEmpty => write!(f, "must not be empty"),
+            Constraint::Length { min, max } => write!(f, "length must be {min}..={max}"),
+            Constraint::Range {
+                min,
+                max,
+                step: None,
+            } => write!(f, "must be {min}..={max}"),
+            Constraint::Range {
+                min,
+                max,
+                step: Some(step),
+            } => write!(f, "must be {min}..={max} in steps of {step}"),
+            Constraint::Before { other } => write!(f, "must be on or before {other}"),
+        }
+    }
+}
+
+/// 送信前のチェック(`RequestBody::new` など)で弾いた入力。
+///
+/// `field` は引数やフィールドの名前(`messages`、`code_verifier` など)、`actual` は実際の値
+/// (件数・文字数の制約では件数・文字数)。フォームの入力欄へ対応付けるためのもの。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub field: String,
+    pub constraint: Constraint,
+    pub actual: serde_json::Value,
+}
+
+impl ValidationError {
+    pub fn new(
+        field: impl Into<String>,
+        constraint: Constraint,
+        actual: impl Into<serde_json::Value>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            constraint,
+            actual: actual.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.field, self.constraint, self.actual)
+    }
+}
+
+impl From<ValidationError> for Box<Error> {
+    fn from(err: ValidationError) -> Self {
+        Box::new(Error::Validation(err))
+    }
+}
+
+pub(crate) fn check_not_empty(field: &str, value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Err(ValidationError::new(field, Constraint::NotEmpty, value));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_length(
+    field: &str,
+    length: usize,
+    min: usize,
+    max: usize,
+) -> Result<(), ValidationError> {
+    if !(min..=max).contains(&length) {
+        return Err(ValidationError::new(
+            field,
+            Constraint::Length { min, max },
+            length,
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn check_range(
+    field: &str,
+    value: i64,
+    min: i64,
+    max: i64,
+    step: Option<i64>,
+) -> Result<(), ValidationError> {
+    if !(min..=max).contains(&value) || step.is_some_and(|step| value % step != 0) {
+        return Err(ValidationError::new(
+            field,
+            Constraint::Range { min, max, step },
+            value,
+        ));
+    }
+    Ok(())
+}
+
+/// 通信エラー(`Error::Reqwest`)の分類。
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone, Copy)]
+pub enum TransportErrorKind {
+    /// 接続できなかった(DNS・TLS・接続拒否・接続タイムアウトなど)。リクエストは LINE に届いていない
+    Connect,
+    /// 接続後のタイムアウト。リクエストが LINE に届いたかは分からない
+    Timeout,
+    /// リクエストの組み立てに失敗した(送信していない)
+    Build,
+    /// レスポンスボディの受信・デコードに失敗した。リクエストは LINE に届いている
+    Decode,
+    /// 上記以外
+    Other,
+}
+
+impl TransportErrorKind {
+    fn from_reqwest(err: &reqwest::Error) -> Self {
+        // 接続タイムアウトは is_connect と is_timeout の両方が立つので、先に接続を見る
+        if err.is_builder() {
+            TransportErrorKind::Build
+        } else if err.is_connect() {
+            TransportErrorKind::Connect
+        } else if err.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if err.is_decode() || err.is_body() {
+            TransportErrorKind::Decode
+        } else {
+            TransportErrorKind::Other
+        }
+    }
+
+    /// 一時的な障害としてリトライしてよいか(組み立て失敗などは何度やっても同じ)。
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            TransportErrorKind::Connect | TransportErrorKind::Timeout | TransportErrorKind::Decode
+        )
+    }
+
+    /// リクエストが LINE に届いた可能性があるか。
+    pub fn may_have_reached_server(self) -> bool {
+        !matches!(
+            self,
+            TransportErrorKind::Connect | TransportErrorKind::Build
+        )
+    }
+}
+
+/// エラーの分類。ステータスコードと LINE の既知のエラーメッセージから判定する。
+///
+/// `ErrorResponse::message` を文字列比較せずに分岐するためのもの。
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone, Copy)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// 送信前のチェックで弾いた(`Error::Invalid`、`Error::Validation`)
+    Invalid,
+    /// 400 のうち、応答トークンが無効・使用済み・期限切れ("Invalid reply token")
+    InvalidReplyToken,
+    /// 上記以外の 400
+    BadRequest,
+    /// 401 またはアクセストークンの認証失敗("Authentication failed...")
+    Unauthorized,
+    /// 403(プランや権限で使えない API)
+    Forbidden,
+    NotFound,
+    /// 409(リトライキーで受理済み)
+    Conflict,
+    /// 413
+    PayloadTooLarge,
+    /// 429 のうち、月間のメッセージ上限に達した("You have reached your monthly limit.")。
+    /// `QuotaGuard` が送信前に拒否した場合(`Error::QuotaExceeded`)も含む
+    MonthlyLimitExceeded,
+    /// 上記以外の 429(レート制限)
+    RateLimited,
+    /// 5xx
+    ServerError,
+    /// 通信エラー(`Error::Reqwest`、詳細は [`Error::transport_kind`])
+    Transport,
+    /// JSON の変換やファイルの読み書きの失敗
+    Decode,
+    /// 上記以外のステータスコード
+    Other,
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Invalid {0}")]
@@ -48,6 +260,15 @@ pub enum Error {
     #[error("serde json {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("io {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Validation {0}")]
+    Validation(ValidationError),
+
+    #[error("QuotaExceeded {0:?}")]
+    QuotaExceeded(QuotaExceeded),
+
     #[error("Line {0:?} {1}")]
     Line(ErrorResponse, StatusCode, LineResponseHeader),
 
@@ -66,6 +287,126 @@ impl Error {
         }
     }
 
+    /// エラーの分類。
+    pub fn kind(&self) -> ErrorKind {
+        let status_code = match self {
+            Error::Invalid(_) | Error::Validation(_) => return ErrorKind::Invalid,
+            Error::QuotaExceeded(_) => return ErrorKind::MonthlyLimitExceeded,
+            Error::Reqwest(_) => return ErrorKind::Transport,
+            Error::Json(_) | Error::Io(_) => return ErrorKind::Decode,
+            Error::Line(response, status_code, _) => {
+                // 既知のメッセージはステータスコードより優先する
+                let message = response.message.to_ascii_lowercase();
+                if message.starts_with("invalid reply token") {
+                    return ErrorKind::InvalidReplyToken;
+                }
+                if message.starts_with("authentication failed") {
+                    return ErrorKind::Unauthorized;
+                }
+                if message.contains("monthly limit") {
+                    return ErrorKind::MonthlyLimitExceeded;
+                }
+                *status_code
+            }
+            Error::LineLogin(_, status_code, _)
+            | Error::OtherJson(_, status_code, _)
+            | Error::OtherText(_, status_code, _) => *status_code,
+        };
+        match status_code {
+            StatusCode::BAD_REQUEST => ErrorKind::BadRequest,
+            StatusCode::UNAUTHORIZED => ErrorKind::Unauthorized,
+            StatusCode::FORBIDDEN => ErrorKind::Forbidden,
+            StatusCode::NOT_FOUND => ErrorKind::NotFound,
+            StatusCode::CONFLICT => ErrorKind::Conflict,
+            StatusCode::PAYLOAD_TOO_LARGE => ErrorKind::PayloadTooLarge,
+            StatusCode::TOO_MANY_REQUESTS => ErrorKind::RateLimited,
+            _ if status_code.is_server_error() => ErrorKind::ServerError,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// レート制限(429)に掛かったか。月間上限は含まない([`Error::is_quota_exceeded`])。
+    pub fn is_rate_limited(&self) -> bool {
+        self.kind() == ErrorKind::RateLimited
+    }
+
+    /// アクセストークンの認証に失敗したか。
+    pub fn is_auth_error(&self) -> bool {
+        self.kind() == ErrorKind::Unauthorized
+    }
+
+    /// 月間のメッセージ上限に達したか(LINE の 429、または `QuotaGuard` による拒否)。
+    pub fn is_quota_exceeded(&self) -> bool {
+        self.kind() == ErrorKind::MonthlyLimitExceeded
+    }
+
+    /// 時間をおけば成功し得るエラーか。
+    ///
+    /// 5xx と 429(月間上限を除く)、および一時的な通信エラー(接続失敗・タイムアウト・受信失敗)。
+    /// 冪等でないリクエストを実際にリトライしてよいかは別途 [`Error::may_have_reached_server`] を見ること。
+    pub fn is_retryable(&self) -> bool {
+        if let Some(kind) = self.transport_kind() {
+            return kind.is_retryable();
+        }
+        self.kind() != ErrorKind::MonthlyLimitExceeded
+            && self.status_code().is_some_and(is_standard_retry)
+    }
+
+    /// `x-line-request-id`(レスポンスを受け取ったエラーのみ)。
+    pub fn request_id(&self) -> Option<&str> {
+        self.line_header()
+            .map(|line_header| line_header.request_id.as_str())
+            .filter(|request_id| !request_id.is_empty())
+    }
+
+    /// `x-line-accepted-request-id`(リトライキーで受理済みの 409 のみ)。
+    pub fn accepted_request_id(&self) -> Option<&str> {
+        self.line_header()
+            .and_then(|line_header| line_header.accepted_request_id.as_deref())
+    }
+
+    /// `ErrorResponse::details`(`Error::Line` 以外、または詳細が無ければ空)。
+    pub fn details(&self) -> &[ErrorDetail] {
+        match self {
+            Error::Line(response, _, _) => response.details.as_deref().unwrap_or_default(),
+            _ => &[],
+        }
+    }
+
+    /// `Error::Reqwest` の分類(それ以外のエラーは `None`)。
+    pub fn transport_kind(&self) -> Option<TransportErrorKind> {
+        match self {
+            Error::Reqwest(err) => Some(TransportErrorKind::from_reqwest(err)),
+            _ => None,
+        }
+    }
+
+    /// このエラーになったリクエストが LINE に届き、処理された可能性があるか。
+    ///
+    /// レスポンスを受け取ったエラーは 429(処理せずに拒否された)以外すべて `true`。
+    /// 通信エラーは [`TransportErrorKind::may_have_reached_server`] に従う。
+    /// 送信前に起きたエラー(`Invalid` など)は `false`。
+    pub fn may_have_reached_server(&self) -> bool {
+        if let Some(kind) = self.transport_kind() {
+            return kind.may_have_reached_server();
+        }
+        match self.status_code() {
+            Some(status_code) => status_code != StatusCode::TOO_MANY_REQUESTS,
+            None => false,
+        }
+    }
+
+    /// LINE から返ったレスポンスヘッダーの情報(レスポンスを受け取ったエラーのみ)。
+    pub fn line_header(&self) -> Option<&LineResponseHeader> {
+        match self {
+            Error::Line(_, _, line_header) => Some(line_header),
+            Error::LineLogin(_, _, line_header) => Some(line_header),
+            Error::OtherJson(_, _, line_header) => Some(line_header),
+            Error::OtherText(_, _, line_header) => Some(line_header),
+            _ => None,
+        }
+    }
+
     pub fn make_json(&self) -> serde_json::Value {
         match self {
             Error::Line(response, status_code, line_header) => {
@@ -96,6 +437,12 @@ impl Error {
                     "line_header": line_header
                 })
             }
+            Error::Validation(validation_error) => {
+                serde_json::json!({
+                    "message": self.to_string(),
+                    "validation": validation_error
+                })
+            }
             _ => {
                 serde_json::json!({
                     "message": self.to_string()
@@ -104,3 +451,131 @@ impl Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn make_line_error(status_code: u16, message: &str) -> Error {
+        Error::Line(
+            serde_json::from_value(json!({"message": message})).unwrap(),
+            StatusCode::from_u16(status_code).unwrap(),
+            LineResponseHeader {
+                request_id: "request-id".to_string(),
+                accepted_request_id: None,
+                retry_after: None,
+                retry_key: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let cases = [
+            (400, "Invalid reply token", ErrorKind::InvalidReplyToken),
+            (
+                400,
+                "The request body has 1 error(s)",
+                ErrorKind::BadRequest,
+            ),
+            (
+                401,
+                "Authentication failed. Confirm that the access token in the authorization header is valid.",
+                ErrorKind::Unauthorized,
+            ),
+            (
+                403,
+                "Access to this API is not available",
+                ErrorKind::Forbidden,
+            ),
+            (
+                429,
+                "You have reached your monthly limit.",
+                ErrorKind::MonthlyLimitExceeded,
+            ),
+            (429, "Too many requests", ErrorKind::RateLimited),
+            (500, "Internal Server Error", ErrorKind::ServerError),
+        ];
+        for (status_code, message, kind) in cases {
+            assert_eq!(
+                make_line_error(status_code, message).kind(),
+                kind,
+                "{message}"
+            );
+        }
+        assert_eq!(Error::Invalid("x".to_string()).kind(), ErrorKind::Invalid);
+        let quota_exceeded = Error::QuotaExceeded(QuotaExceeded {
+            cost: 2,
+            remaining: 1,
+            limit: 100,
+        });
+        assert!(quota_exceeded.is_quota_exceeded());
+        assert!(!quota_exceeded.is_retryable());
+    }
+
+    #[test]
+    fn test_validation_error() {
+        let err = check_length("messages", 6, 1, 5).unwrap_err();
+        assert_eq!(err.to_string(), "messages length must be 1..=5: 6");
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "field": "messages",
+                "constraint": {"type": "length", "min": 1, "max": 5},
+                "actual": 6
+            })
+        );
+        let err = check_range("loading_seconds", 7, 5, 60, Some(5)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "loading_seconds must be 5..=60 in steps of 5: 7"
+        );
+        assert!(check_range("loading_seconds", 10, 5, 60, Some(5)).is_ok());
+        assert!(check_not_empty("to", "").is_err());
+
+        let err: Box<Error> = err.into();
+        assert_eq!(err.kind(), ErrorKind::Invalid);
+        assert_eq!(err.make_json()["validation"]["field"], "loading_seconds");
+    }
+
+    #[test]
+    fn test_error_helpers() {
+        let error = make_line_error(429, "Too many requests");
+        assert!(error.is_rate_limited());
+        assert!(error.is_retryable());
+        assert!(!error.is_quota_exceeded());
+        assert_eq!(error.request_id(), Some("request-id"));
+        assert_eq!(error.accepted_request_id(), None);
+
+        // 月間上限はリトライしても成功しない
+        let error = make_line_error(429, "You have reached your monthly limit.");
+        assert!(error.is_quota_exceeded());
+        assert!(!error.is_rate_limited());
+        assert!(!error.is_retryable());
+
+        let error = make_line_error(401, "Authentication failed");
+        assert!(error.is_auth_error());
+        assert!(!error.is_retryable());
+
+        let error = Error::Line(
+            serde_json::from_value(json!({
+                "message": "The request body has 1 error(s)",
+                "details": [{"message": "May not be empty", "property": "messages[0].text"}]
+            }))
+            .unwrap(),
+            StatusCode::BAD_REQUEST,
+            LineResponseHeader {
+                request_id: String::new(),
+                accepted_request_id: None,
+                retry_after: None,
+                retry_key: None,
+            },
+        );
+        assert_eq!(error.details().len(), 1);
+        assert_eq!(error.details()[0].property, "messages[0].text");
+        assert_eq!(error.request_id(), None);
+        assert!(Error::Invalid("x".to_string()).details().is_empty());
+    }
+}
@@ -1,18 +1,25 @@
 use std::time::Duration;
 
-use rand::{RngExt, rngs::StdRng};
 use reqwest::{
     RequestBuilder, Response, StatusCode,
     header::{self, AUTHORIZATION, HeaderMap},
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tracing::Instrument;
 
-use crate::error::{Error, ErrorResponse, LineLoginErrorResponse};
+use crate::{
+    error::{Error, ErrorResponse, LineLoginErrorResponse},
+    rate_limit::RateLimitGroup,
+    retry::{RetryContext, RetryDecision},
+};
 
 pub mod error;
 pub mod line_login;
 pub mod messaging_api;
 pub mod option;
+pub mod rate_limit;
+pub mod retry;
+mod telemetry;
 
 #[cfg(feature = "mock")]
 pub mod mock;
@@ -31,6 +38,30 @@ pub struct LineResponseHeader {
     pub request_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub accepted_request_id: Option<String>,
+    /// `Retry-After` ヘッダー(秒数または HTTP-date)を待ち時間に換算した値
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<Duration>,
+    /// このリクエストに付けた `X-Line-Retry-Key`(自動生成したものを含む)。
+    /// 保存しておけば、プロセスの再起動後も同じキーで再送できる
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_key: Option<String>,
+}
+
+/// `X-Line-Retry-Key` に使う UUIDv4 を生成する。
+///
+/// 通信エラーなどでレスポンスを受け取れなかった場合は [`LineResponseHeader::retry_key`] で
+/// キーを受け取れないため、再起動をまたいで再送したい場合は先にこれで生成・保存してから渡す。
+pub fn new_retry_key() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+// リトライキーを決める(指定があればそのまま)。
+// 自動生成が有効でリトライの可能性がある場合だけ生成する(リトライしないなら付けても意味がない)
+pub(crate) fn resolve_retry_key(
+    retry_key: Option<String>,
+    options: &LineOptions,
+) -> Option<String> {
+    retry_key.or_else(|| (options.get_auto_retry_key() && options.may_retry()).then(new_retry_key))
 }
 
 // 以前ここにあった `LineRequestLog` / `LineResponseLog` / `ResponseBody` /
@@ -40,6 +71,10 @@ pub(crate) fn make_url(postfix_url: &str, options: &LineOptions) -> String {
     format!("{}{postfix_url}", options.resolve_prefix_url())
 }
 
+pub(crate) fn make_data_url(postfix_url: &str, options: &LineOptions) -> String {
+    format!("{}{postfix_url}", options.resolve_data_prefix_url())
+}
+
 pub(crate) fn apply_auth(builder: RequestBuilder, channel_access_token: &str) -> RequestBuilder {
     builder.header(AUTHORIZATION, format!("Bearer {channel_access_token}"))
 }
@@ -81,32 +116,57 @@ pub(crate) fn make_line_header(response: &Response) -> LineResponseHeader {
             })
             .to_string()
     });
+    let retry_after = headers
+        .get(header::RETRY_AFTER)
+        .and_then(|it| it.to_str().ok())
+        .and_then(|it| parse_retry_after(it, chrono::Utc::now()));
     LineResponseHeader {
         request_id: request_id.to_owned(),
         accepted_request_id,
+        retry_after,
+        retry_key: None,
+    }
+}
+
+// Retry-After は秒数か HTTP-date のどちらか
+// https://httpwg.org/specs/rfc9110.html#field.retry-after
+pub(crate) fn parse_retry_after(
+    value: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
     }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // 過去の日時ならすぐにリトライしてよい
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
 }
 
-pub(crate) fn calc_retry_duration(
-    retry_duration: Duration,
-    try_count: u32,
-    rng: &mut StdRng,
-) -> Duration {
-    // Jitter
-    let jitter = Duration::from_millis(rng.random_range(0..100));
-
-    // exponential backoff
-    // 0の時1回、1の時2回、2の時4回、3の時8回
-    let retry_count = 2u64.pow(try_count) as u32;
-    retry_duration * retry_count + jitter
+// 送信前のリクエストを複製して中身を確認する(マルチパート等で複製できない場合は None)
+fn peek_request(builder: &RequestBuilder) -> Option<reqwest::Request> {
+    builder.try_clone().and_then(|b| b.build().ok())
+}
+
+// 同じリクエストを繰り返しても結果が変わらないメソッドか。
+// multipart のように複製できないリクエストでも判定できるよう、エンドポイントのメソッドで見る
+fn is_idempotent_method(method: &str) -> bool {
+    matches!(method, "GET" | "HEAD" | "PUT" | "DELETE" | "OPTIONS")
 }
 
 // APIを実行して一時的にエラーをハンドリングする
+// レスポンスを受け取れた場合は、成否にかかわらず response_headers にヘッダーを複製する(リトライ判定用)
 pub(crate) async fn execute_api_raw(
     builder: RequestBuilder,
     allow_conflict: bool,
+    sent_retry_key: Option<&str>,
     options: &LineOptions,
     request_value: &serde_json::Value,
+    response_headers: &mut HeaderMap,
 ) -> Result<(serde_json::Value, LineResponseHeader, StatusCode), Box<Error>> {
     let need_log = options.on_request.is_some() || options.on_response.is_some();
 
@@ -153,12 +213,9 @@ pub(crate) async fn execute_api_raw(
         .await
         .map_err(|err| Box::new(Error::Reqwest(err)))?;
     let status_code = response.status();
-    let line_header = make_line_header(&response);
-    let response_headers = if options.on_response.is_some() {
-        response.headers().clone()
-    } else {
-        HeaderMap::new()
-    };
+    let mut line_header = make_line_header(&response);
+    line_header.retry_key = sent_retry_key.map(|it| it.to_owned());
+    *response_headers = response.headers().clone();
     // ボディ読取失敗は握り潰さず伝播する(読めなかったボディは観測経路にも乗せない)。
     let text = response
         .text()
@@ -176,7 +233,7 @@ pub(crate) async fn execute_api_raw(
             cb(
                 &LineRequestLog::new(captured.as_ref(), request_value, redacted_body_keys),
                 &LineResponseLog::new(
-                    &response_headers,
+                    response_headers,
                     response_body,
                     status_code,
                     redacted_body_keys,
@@ -215,43 +272,133 @@ pub(crate) async fn execute_api_raw(
 
 const HEADER_RETRY_KEY: &str = "X-Line-Retry-Key";
 
-pub(crate) async fn execute_api<T, F>(
+/// `execute_api` に渡すエンドポイントの情報(トレースの属性に使う)。
+///
+/// 各エンドポイントのモジュールで `const ENDPOINT` として定義する。
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Endpoint {
+    /// モジュール名(`post_v2_bot_message_push` など)
+    pub(crate) name: &'static str,
+    pub(crate) method: &'static str,
+    /// パスのテンプレート(`/v2/bot/profile/{userId}` など)
+    pub(crate) route: &'static str,
+}
+
+impl Endpoint {
+    pub(crate) const fn new(name: &'static str, method: &'static str, route: &'static str) -> Self {
+        Self {
+            name,
+            method,
+            route,
+        }
+    }
+}
+
+pub(crate) async fn execute_api<T>(
+    endpoint: Endpoint,
     f: impl Fn() -> RequestBuilder,
     options: &LineOptions,
-    is_retry: F,
     retry_key: Option<String>,
     request_value_fn: impl FnOnce() -> serde_json::Value,
 ) -> Result<(T, LineResponseHeader), Box<Error>>
 where
     T: DeserializeOwned,
-    F: Fn(StatusCode) -> bool,
+{
+    execute_api_with_status(endpoint, f, options, retry_key, request_value_fn)
+        .await
+        .map(|(data, line_header, _status_code)| (data, line_header))
+}
+
+// execute_api と同じだが、成功時のステータスコードも返す(409 の受理済み判定などに使う)
+pub(crate) async fn execute_api_with_status<T>(
+    endpoint: Endpoint,
+    f: impl Fn() -> RequestBuilder,
+    options: &LineOptions,
+    retry_key: Option<String>,
+    request_value_fn: impl FnOnce() -> serde_json::Value,
+) -> Result<(T, LineResponseHeader, StatusCode), Box<Error>>
+where
+    T: DeserializeOwned,
+{
+    // 呼び出し全体を line_api スパンで囲む(属性は telemetry を参照)
+    let span = telemetry::request_span(&endpoint);
+    let started_at = std::time::Instant::now();
+    let res = execute_api_attempts(endpoint, f, options, retry_key, request_value_fn)
+        .instrument(span.clone())
+        .await;
+    telemetry::record_result(&span, &res, started_at.elapsed());
+    res
+}
+
+async fn execute_api_attempts<T>(
+    endpoint: Endpoint,
+    f: impl Fn() -> RequestBuilder,
+    options: &LineOptions,
+    retry_key: Option<String>,
+    request_value_fn: impl FnOnce() -> serde_json::Value,
+) -> Result<(T, LineResponseHeader, StatusCode), Box<Error>>
+where
+    T: DeserializeOwned,
 {
     // リトライ処理
     // https://developers.line.biz/ja/docs/messaging-api/retrying-api-request/#flow-of-api-request-retry
-    let mut res = Err(Error::Invalid("fail loop".to_string()));
-    let try_count = options.get_try_count();
-    let retry_duration: Duration = options.get_retry_duration();
+    let retry_policy = options.get_retry_policy();
+    let max_retry_after = options.get_max_retry_after();
+    // リトライキー付与はリトライの可能性がある場合のみ
+    let may_retry = options.may_retry();
+    let sent_retry_key = retry_key.as_deref().filter(|_| may_retry);
     // コールバック設定時のみ request body をシリアライズする(未設定時の無駄を避ける)。
     let request_value = if options.on_request.is_some() || options.on_response.is_some() {
         request_value_fn()
     } else {
         serde_json::Value::Null
     };
-    let mut rng: StdRng = rand::make_rng();
-    for i in 0..try_count {
+    let started_at = std::time::Instant::now();
+    let mut previous_delay = Duration::ZERO;
+    let mut attempt: u32 = 0;
+    let res = loop {
+        attempt += 1;
         // リクエスト準備
         let mut builder = f();
-        // リトライキー付与は try_count が 2 以上(リトライあり)の場合のみ
-        if let Some(retry_key) = &retry_key
-            && try_count > 1
-        {
+        if let Some(retry_key) = sent_retry_key {
             builder = builder.header(HEADER_RETRY_KEY, retry_key);
         }
-        match execute_api_raw(builder, retry_key.is_some(), options, &request_value).await {
+        let request = peek_request(&builder);
+        let span = tracing::Span::current();
+        span.record("line.attempts", attempt);
+        if attempt == 1 {
+            // multipart など複製できないリクエストでは request が None になる
+            telemetry::record_request(&span, &endpoint, request.as_ref(), options);
+        }
+        // リトライキー付きなら LINE 側で重複が弾かれるので、POST でも冪等として扱える
+        let idempotent = sent_retry_key.is_some() || is_idempotent_method(endpoint.method);
+        // クライアント側のレート制限(設定時のみ)。リトライも 1 回の送信として数える
+        if let Some(rate_limiter) = options.get_rate_limiter() {
+            let group = request
+                .as_ref()
+                .map(|it| RateLimitGroup::from_path(it.url().path()))
+                .unwrap_or(RateLimitGroup::Other);
+            rate_limiter.acquire(group).await;
+        }
+        let mut response_headers = HeaderMap::new();
+        let attempt_span = telemetry::attempt_span(attempt);
+        let attempt_started_at = std::time::Instant::now();
+        let result = execute_api_raw(
+            builder,
+            retry_key.is_some(),
+            sent_retry_key,
+            options,
+            &request_value,
+            &mut response_headers,
+        )
+        .instrument(attempt_span.clone())
+        .await;
+        telemetry::record_result(&attempt_span, &result, attempt_started_at.elapsed());
+        match result {
             Ok((json, line_header, status_code)) => {
-                res = match serde_json::from_value(json.clone()) {
+                break match serde_json::from_value(json.clone()) {
                     // フォーマットがあっている
-                    Ok(data) => Ok((data, line_header)),
+                    Ok(data) => Ok((data, line_header, status_code)),
                     // フォーマットが違っている場合
                     Err(_err) => match serde_json::from_value::<ErrorResponse>(json.clone()) {
                         Ok(error_response) => {
@@ -267,39 +414,56 @@ where
                         }
                     },
                 };
-                break;
             }
             Err(err) => {
                 tracing::debug!("error: {:?}", err);
 
-                // ステータスコードによってはリトライを行わない
-                if !is_retry(
-                    err.status_code()
-                        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
-                ) {
-                    // リトライしない
-                    res = Err(*err);
-                    break;
+                // 冪等でないリクエストが LINE に届いた可能性があるなら、二重送信を避けるため
+                // 方針によらずリトライしない
+                if !idempotent && err.may_have_reached_server() {
+                    break Err(*err);
                 }
 
-                if i + 1 >= try_count {
-                    // リトライ回数がオーバーしたので失敗にする
-                    res = Err(*err);
-                } else if !retry_duration.is_zero() {
-                    // リトライ間隔がある場合は待つ
-                    tokio::time::sleep(calc_retry_duration(retry_duration, i as u32, &mut rng))
-                        .await;
+                // Retry-After は上限で切り詰めて方針へ渡す(上限 0 なら無視する)
+                let retry_after = err
+                    .line_header()
+                    .and_then(|it| it.retry_after)
+                    .filter(|_| !max_retry_after.is_zero())
+                    .map(|it| it.min(max_retry_after));
+                let context = RetryContext {
+                    attempt,
+                    error: &err,
+                    headers: &response_headers,
+                    elapsed: started_at.elapsed(),
+                    previous_delay,
+                    retry_after,
+                    idempotent,
+                };
+                match retry_policy.next(&context) {
+                    RetryDecision::Stop => break Err(*err),
+                    RetryDecision::Retry(delay) => {
+                        if !delay.is_zero() {
+                            tokio::time::sleep(delay).await;
+                        }
+                        previous_delay = delay;
+                    }
                 }
             }
         }
-    }
+    };
     res.map_err(Box::new)
 }
 
-#[cfg(test)]
+// テストはすべて mockito を使うため mock feature 有効時のみビルドする
+#[cfg(all(test, feature = "mock"))]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    const TEST_GET: Endpoint = Endpoint::new("test", "GET", "/test");
+    const TEST_POST: Endpoint = Endpoint::new("test", "POST", "/test");
+
     // コールバック未設定なら request_value_fn は呼ばれない(無駄なシリアライズを避ける)。
     #[cfg(feature = "mock")]
     #[tokio::test]
@@ -316,9 +480,9 @@ mod tests {
 
         // コールバック未設定なので、呼ばれたら panic するクロージャでも問題なく完了する
         let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
             || reqwest::Client::new().get(&url),
             &options,
-            is_standard_retry,
             None,
             || panic!("request_value_fn must not be called when no callback is set"),
         )
@@ -347,9 +511,9 @@ mod tests {
             .build();
 
         let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
             || reqwest::Client::new().get(&url),
             &options,
-            is_standard_retry,
             None,
             || serde_json::Value::Null,
         )
@@ -378,9 +542,9 @@ mod tests {
         let options = LineOptions::default();
 
         let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_POST,
             || reqwest::Client::new().post(&url),
             &options,
-            is_standard_retry,
             None,
             || serde_json::Value::Null,
         )
@@ -421,9 +585,9 @@ mod tests {
         let options = LineOptions::default();
 
         let result: Result<(Dummy, LineResponseHeader), _> = execute_api(
+            TEST_POST,
             || reqwest::Client::new().post(&url),
             &options,
-            is_standard_retry,
             None,
             || serde_json::Value::Null,
         )
@@ -458,9 +622,9 @@ mod tests {
         let options = LineOptions::default();
 
         let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_POST,
             || reqwest::Client::new().post(&url),
             &options,
-            is_standard_retry,
             None,
             || serde_json::Value::Null,
         )
@@ -494,9 +658,9 @@ mod tests {
         let options = LineOptions::default();
 
         let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_POST,
             || reqwest::Client::new().post(&url),
             &options,
-            is_standard_retry,
             None,
             || serde_json::Value::Null,
         )
@@ -514,4 +678,551 @@ mod tests {
         }
         mock.assert_async().await;
     }
+
+    // Retry-After は秒数と HTTP-date の両方を受け付ける。
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-10-18T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 18 Oct 2026 00:00:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // 過去の日時は待たない
+        assert_eq!(
+            parse_retry_after("Sat, 17 Oct 2026 23:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    // 429 の Retry-After はバックオフより優先し、上限で切り詰める。
+    // retry_duration を長くしてあるので、バックオフで待つとテストが終わらない。
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_retry_after_overrides_backoff() {
+        for (retry_after, max_retry_after) in [("0", None), ("3600", Some(10u64))] {
+            let mut server = mockito::Server::new_async().await;
+            let too_many = server
+                .mock("GET", "/test")
+                .with_status(429)
+                .with_header("Retry-After", retry_after)
+                .with_body(r#"{"message":"Too many requests"}"#)
+                .create_async()
+                .await;
+            let ok = server
+                .mock("GET", "/test")
+                .with_status(200)
+                .with_body("{}")
+                .create_async()
+                .await;
+            let url = format!("{}/test", server.url());
+            let mut builder = LineOptions::builder()
+                .with_try_count(2)
+                .with_retry_duration(Duration::from_secs(3600));
+            if let Some(max_retry_after) = max_retry_after {
+                builder = builder.with_max_retry_after(Duration::from_millis(max_retry_after));
+            }
+            let options = builder.build();
+
+            let result: Result<(serde_json::Value, LineResponseHeader), _> = tokio::time::timeout(
+                Duration::from_secs(5),
+                execute_api(
+                    TEST_GET,
+                    || reqwest::Client::new().get(&url),
+                    &options,
+                    None,
+                    || serde_json::Value::Null,
+                ),
+            )
+            .await
+            .expect("Retry-After must be used instead of the backoff");
+
+            assert!(result.is_ok());
+            too_many.assert_async().await;
+            ok.assert_async().await;
+        }
+    }
+
+    // 失敗したレスポンスの Retry-After は Error の line_header から参照できる。
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_retry_after_in_error_header() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test")
+            .with_status(429)
+            .with_header("Retry-After", "7")
+            .with_body(r#"{"message":"Too many requests"}"#)
+            .create_async()
+            .await;
+        let url = format!("{}/test", server.url());
+
+        let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
+            || reqwest::Client::new().get(&url),
+            &LineOptions::default(),
+            None,
+            || serde_json::Value::Null,
+        )
+        .await;
+
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.line_header().and_then(|it| it.retry_after),
+            Some(Duration::from_secs(7))
+        );
+        mock.assert_async().await;
+    }
+
+    // レート制限はパスからグループを判定し、設定したグループだけ待たせる。
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_rate_limit_by_path() {
+        use crate::rate_limit::{RateLimit, RateLimitGroup};
+
+        let mut server = mockito::Server::new_async().await;
+        let push = server
+            .mock("POST", "/v2/bot/message/push")
+            .with_status(200)
+            .with_body("{}")
+            .expect(3)
+            .create_async()
+            .await;
+        let reply = server
+            .mock("POST", "/v2/bot/message/reply")
+            .with_status(200)
+            .with_body("{}")
+            .expect(3)
+            .create_async()
+            .await;
+        let options = LineOptions::builder()
+            .with_rate_limit(RateLimitGroup::Push, RateLimit::per_second(10))
+            .build();
+        // クローンしてもバケットは共有される
+        let cloned = options.clone();
+
+        let call = |path: &'static str, options: LineOptions| {
+            let url = format!("{}{path}", server.url());
+            async move {
+                let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+                    TEST_POST,
+                    || reqwest::Client::new().post(&url),
+                    &options,
+                    None,
+                    || serde_json::Value::Null,
+                )
+                .await;
+                assert!(result.is_ok());
+            }
+        };
+
+        // reply(Other)は制限を設定していないのでバケットを消費しない
+        for _ in 0..3 {
+            call("/v2/bot/message/reply", options.clone()).await;
+        }
+        let limiter = options.get_rate_limiter().unwrap();
+        assert_eq!(limiter.get_limit(RateLimitGroup::Other), None);
+
+        // バケットを空にしてから 3 回送ると、1 回ごとに補充(100ms)を待つ
+        for _ in 0..10 {
+            limiter.acquire(RateLimitGroup::Push).await;
+        }
+        let start = std::time::Instant::now();
+        call("/v2/bot/message/push", options.clone()).await;
+        call("/v2/bot/message/push", cloned.clone()).await;
+        call("/v2/bot/message/push", cloned).await;
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        push.assert_async().await;
+        reply.assert_async().await;
+    }
+
+    // 独自の RetryPolicy には試行回数・エラー・レスポンスヘッダーが渡り、判断どおりに止まる。
+    // レスポンスを受け取れない通信エラーも Error::Reqwest のまま渡る。
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_custom_retry_policy() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::retry::{RetryContext, RetryDecision, RetryPolicy};
+
+        // (試行回数, ステータスコード, x-test ヘッダー)
+        type Seen = Vec<(u32, Option<u16>, Option<String>)>;
+
+        #[derive(Debug, Default)]
+        struct Recorder {
+            seen: Arc<Mutex<Seen>>,
+        }
+        impl RetryPolicy for Recorder {
+            fn next(&self, context: &RetryContext) -> RetryDecision {
+                self.seen.lock().unwrap().push((
+                    context.attempt,
+                    context.error.status_code().map(|it| it.as_u16()),
+                    context
+                        .headers
+                        .get("x-test")
+                        .map(|it| it.to_str().unwrap().to_owned()),
+                ));
+                if context.attempt < 3 {
+                    RetryDecision::Retry(Duration::ZERO)
+                } else {
+                    RetryDecision::Stop
+                }
+            }
+        }
+
+        // 4xx でも方針がリトライと判断すればリトライする
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test")
+            .with_status(400)
+            .with_header("x-test", "abc")
+            .with_body(r#"{"message":"bad"}"#)
+            .expect(3)
+            .create_async()
+            .await;
+        let url = format!("{}/test", server.url());
+        let recorder = Recorder::default();
+        let seen = recorder.seen.clone();
+        let options = LineOptions::builder().with_retry_policy(recorder).build();
+
+        let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
+            || reqwest::Client::new().get(&url),
+            &options,
+            None,
+            || serde_json::Value::Null,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *seen.lock().unwrap(),
+            (1..=3)
+                .map(|it| (it, Some(400), Some("abc".to_owned())))
+                .collect::<Vec<_>>()
+        );
+        mock.assert_async().await;
+
+        // 接続できない場合はステータスもヘッダーも無い
+        let recorder = Recorder::default();
+        let seen = recorder.seen.clone();
+        let options = LineOptions::builder().with_retry_policy(recorder).build();
+        let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
+            || reqwest::Client::new().get("http://127.0.0.1:1/test"),
+            &options,
+            None,
+            || serde_json::Value::Null,
+        )
+        .await;
+
+        assert!(matches!(*result.unwrap_err(), Error::Reqwest(_)));
+        assert_eq!(
+            *seen.lock().unwrap(),
+            (1..=3).map(|it| (it, None, None)).collect::<Vec<_>>()
+        );
+    }
+
+    // 通信エラーを接続失敗・タイムアウト・組み立て失敗に分類する。
+    #[tokio::test]
+    async fn test_transport_error_kind() {
+        use crate::error::TransportErrorKind;
+
+        // 接続拒否
+        let err = Error::Reqwest(
+            reqwest::Client::new()
+                .get("http://127.0.0.1:1/test")
+                .send()
+                .await
+                .unwrap_err(),
+        );
+        assert_eq!(err.transport_kind(), Some(TransportErrorKind::Connect));
+        assert!(!err.may_have_reached_server());
+
+        // 組み立て失敗(URL が不正)
+        let err = Error::Reqwest(
+            reqwest::Client::new()
+                .get("not a url")
+                .send()
+                .await
+                .unwrap_err(),
+        );
+        assert_eq!(err.transport_kind(), Some(TransportErrorKind::Build));
+        assert!(!err.may_have_reached_server());
+
+        // 接続は受け付けるが応答しないサーバー
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/test", listener.local_addr().unwrap());
+        let _server = tokio::spawn(async move {
+            let mut sockets = vec![];
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        let err = Error::Reqwest(
+            reqwest::Client::new()
+                .post(&url)
+                .timeout(Duration::from_millis(50))
+                .send()
+                .await
+                .unwrap_err(),
+        );
+        assert_eq!(err.transport_kind(), Some(TransportErrorKind::Timeout));
+        assert!(err.may_have_reached_server());
+    }
+
+    // リトライキーの無い POST は、LINE に届いた可能性があれば方針によらずリトライしない。
+    // 429(処理されずに拒否)ならリトライし、リトライキー付きなら 5xx でもリトライする。
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_non_idempotent_post_is_not_retried() {
+        for (status_code, retry_key, expected_hits) in [
+            (500, None, 1),
+            (429, None, 3),
+            (500, Some("retry-key-1".to_string()), 3),
+        ] {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("POST", "/test")
+                .with_status(status_code)
+                .with_body(r#"{"message":"error"}"#)
+                .expect(expected_hits)
+                .create_async()
+                .await;
+            let url = format!("{}/test", server.url());
+            let options = LineOptions::builder().with_try_count(3).build();
+
+            let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+                TEST_POST,
+                || reqwest::Client::new().post(&url),
+                &options,
+                retry_key,
+                || serde_json::Value::Null,
+            )
+            .await;
+
+            assert!(result.is_err());
+            mock.assert_async().await;
+        }
+
+        // GET は 5xx でもリトライする
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test")
+            .with_status(500)
+            .with_body(r#"{"message":"error"}"#)
+            .expect(3)
+            .create_async()
+            .await;
+        let url = format!("{}/test", server.url());
+        let options = LineOptions::builder().with_try_count(3).build();
+        let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
+            || reqwest::Client::new().get(&url),
+            &options,
+            None,
+            || serde_json::Value::Null,
+        )
+        .await;
+        assert!(result.is_err());
+        mock.assert_async().await;
+    }
+
+    type RecordedSpans = Vec<(tracing::span::Id, String, HashMap<String, String>)>;
+
+    // 作られたスパンと記録された属性を集める
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        spans: std::sync::Arc<std::sync::Mutex<RecordedSpans>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanRecorder {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            self.spans.lock().unwrap().push((
+                id.clone(),
+                attrs.metadata().name().to_string(),
+                fields,
+            ));
+        }
+
+        fn on_record(
+            &self,
+            id: &tracing::span::Id,
+            values: &tracing::span::Record<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut spans = self.spans.lock().unwrap();
+            if let Some((_, _, fields)) = spans.iter_mut().find(|(it, _, _)| it == id) {
+                values.record(&mut FieldVisitor(fields));
+            }
+        }
+    }
+
+    impl SpanRecorder {
+        fn spans(&self, name: &str) -> Vec<HashMap<String, String>> {
+            self.spans
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, it, _)| it == name)
+                .map(|(_, _, fields)| fields.clone())
+                .collect()
+        }
+    }
+
+    // エンドポイント名のスパンに、メソッド・パス・マスク済みクエリ・ステータスコードを記録する
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_execute_api_span_for_endpoint() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let recorder = SpanRecorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let mut server = mockito::Server::new_async().await;
+        let mock =
+            crate::mock::line_login::get_oauth2_v2_1_verify::make_mock(&mut server, None).await;
+        crate::line_login::get_oauth2_v2_1_verify::execute(
+            "test_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+        mock.assert_async().await;
+
+        let spans = recorder.spans("line_api");
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span["otel.name"], "get_oauth2_v2_1_verify");
+        assert_eq!(span["line.endpoint"], "get_oauth2_v2_1_verify");
+        assert_eq!(span["http.request.method"], "GET");
+        assert_eq!(span["http.route"], "/oauth2/v2.1/verify");
+        assert_eq!(span["url.path"], "/oauth2/v2.1/verify");
+        // access_token は REDACTED_BODY_KEYS と同じくマスクする
+        assert_eq!(span["url.query"], "access_token=***");
+        assert_eq!(span["http.response.status_code"], "200");
+        assert_eq!(span["line.attempts"], "1");
+        assert!(span.contains_key("latency_ms"));
+        assert!(!span.contains_key("otel.status_code"));
+    }
+
+    // 複製できない multipart のリクエストでも、エンドポイントのメソッドとパスを記録する
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_execute_api_span_for_multipart() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        use crate::messaging_api::post_v2_bot_audience_group_upload_by_file;
+
+        let recorder = SpanRecorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let mut server = mockito::Server::new_async().await;
+        let mock =
+            crate::mock::messaging_api::post_v2_bot_audience_group_upload_by_file::make_mock(
+                &mut server,
+                None,
+            )
+            .await;
+        let body = post_v2_bot_audience_group_upload_by_file::RequestBody::new(
+            "test audience by file",
+            vec!["U111".to_string(), "U222".to_string()],
+        )
+        .unwrap();
+        post_v2_bot_audience_group_upload_by_file::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder()
+                .with_data_prefix_url(server.url())
+                .build(),
+        )
+        .await
+        .unwrap();
+        mock.assert_async().await;
+
+        let span = &recorder.spans("line_api")[0];
+        assert_eq!(
+            span["otel.name"],
+            "post_v2_bot_audience_group_upload_by_file"
+        );
+        assert_eq!(span["http.request.method"], "POST");
+        assert_eq!(span["http.route"], "/v2/bot/audienceGroup/upload/byFile");
+        assert_eq!(span["url.path"], "/v2/bot/audienceGroup/upload/byFile");
+        assert_eq!(span["http.response.status_code"], "202");
+    }
+
+    // 試行ごとの子スパンと、失敗時の error.type / request id を記録する
+    #[cfg(feature = "mock")]
+    #[tokio::test]
+    async fn test_execute_api_span_for_retries() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let recorder = SpanRecorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/test")
+            .with_status(500)
+            .with_header("x-line-request-id", "request-id-1")
+            .with_body(r#"{"message":"error"}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let url = format!("{}/test", server.url());
+        let options = LineOptions::builder().with_try_count(2).build();
+        let result: Result<(serde_json::Value, LineResponseHeader), _> = execute_api(
+            TEST_GET,
+            || reqwest::Client::new().get(&url),
+            &options,
+            None,
+            || serde_json::Value::Null,
+        )
+        .await;
+        assert!(result.is_err());
+        mock.assert_async().await;
+
+        let span = &recorder.spans("line_api")[0];
+        assert_eq!(span["otel.name"], "test");
+        assert_eq!(span["line.attempts"], "2");
+        assert_eq!(span["otel.status_code"], "ERROR");
+        assert_eq!(span["error.type"], "ServerError");
+        assert_eq!(span["http.response.status_code"], "500");
+        assert_eq!(span["line.request_id"], "request-id-1");
+
+        let attempts = recorder.spans("line_api.attempt");
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[1]["line.attempt"], "2");
+        assert_eq!(attempts[1]["http.response.status_code"], "500");
+    }
 }
@@ -2,7 +2,7 @@ use base64::Engine;
 use sha2::{Digest, Sha256};
 use url::Url;
 
-use crate::error::Error;
+use crate::error::{Error, check_length};
 
 pub mod get_friendship_v1_status;
 pub mod get_oauth2_v2_1_userinfo;
@@ -50,11 +50,7 @@ pub fn oauth_url(
 
         if let Some(code_verifier) = code_verifier {
             let code_verifier = code_verifier.into();
-            if !(43..=128).contains(&code_verifier.len()) {
-                return Err(Box::new(Error::Invalid(
-                    "code_verifier is length invalid".to_string(),
-                )));
-            }
+            check_length("code_verifier", code_verifier.len(), 43, 128)?;
             let code_challenge = Sha256::digest(code_verifier.as_bytes());
             let code_challenge =
                 base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(code_challenge);
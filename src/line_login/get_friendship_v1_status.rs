@@ -2,12 +2,13 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/line-login/#get-friendship-status
 const URL: &str = "/friendship/v1/status";
+const ENDPOINT: Endpoint = Endpoint::new("get_friendship_v1_status", "GET", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -31,9 +32,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(access_token, options),
         options,
-        is_standard_retry,
         None,
         || serde_json::Value::Null,
     )
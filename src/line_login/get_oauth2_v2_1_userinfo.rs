@@ -2,12 +2,14 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/line-login/#userinfo
 const URL: &str = "/oauth2/v2.1/userinfo";
+const ENDPOINT_GET: Endpoint = Endpoint::new("get_oauth2_v2_1_userinfo", "GET", URL);
+const ENDPOINT_POST: Endpoint = Endpoint::new("get_oauth2_v2_1_userinfo", "POST", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResponseBody {
@@ -43,9 +45,9 @@ pub async fn execute_get(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT_GET,
         || build_get(access_token, options),
         options,
-        is_standard_retry,
         None,
         || serde_json::Value::Null,
     )
@@ -57,9 +59,9 @@ pub async fn execute_post(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT_POST,
         || build_post(access_token, options),
         options,
-        is_standard_retry,
         None,
         || serde_json::Value::Null,
     )
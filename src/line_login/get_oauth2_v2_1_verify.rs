@@ -2,12 +2,12 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_timeout, error::Error, execute_api, is_standard_retry,
-    make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_timeout, error::Error, execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/line-login/#verify-access-token
 const URL: &str = "/oauth2/v2.1/verify";
+const ENDPOINT: Endpoint = Endpoint::new("get_oauth2_v2_1_verify", "GET", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResponseBody {
@@ -32,9 +32,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(access_token, options),
         options,
-        is_standard_retry,
         None,
         || serde_json::Value::Null,
     )
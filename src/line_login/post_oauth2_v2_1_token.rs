@@ -2,12 +2,12 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_timeout, error::Error, execute_api, is_standard_retry,
-    make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_timeout, error::Error, execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/line-login/#issue-access-token
 const URL: &str = "/oauth2/v2.1/token";
+const ENDPOINT: Endpoint = Endpoint::new("post_oauth2_v2_1_token", "POST", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "grant_type")]
@@ -57,9 +57,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(request_body, options),
         options,
-        is_standard_retry,
         None,
         || crate::serialize_log_body(request_body),
     )
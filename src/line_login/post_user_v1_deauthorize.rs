@@ -2,12 +2,13 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/line-login/#deauthorize-app
 const URL: &str = "/user/v1/deauthorize";
+const ENDPOINT: Endpoint = Endpoint::new("post_user_v1_deauthorize", "POST", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestBody {
@@ -42,9 +43,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(channel_access_token, request_body, options),
         options,
-        is_standard_retry,
         None,
         || crate::serialize_log_body(request_body),
     )
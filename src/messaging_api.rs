@@ -1,9 +1,57 @@
+pub mod aggregation_report;
+pub mod audience_group;
+pub mod audience_group_file_upload;
+pub mod chat_source;
+pub mod delete_v2_bot_audience_group;
+pub mod get_v2_bot_audience_group;
+pub mod get_v2_bot_audience_group_list;
+pub mod get_v2_bot_audience_group_shared;
+pub mod get_v2_bot_audience_group_shared_list;
+pub mod get_v2_bot_followers_ids;
+pub mod get_v2_bot_group_member;
+pub mod get_v2_bot_group_members_count;
+pub mod get_v2_bot_group_members_ids;
+pub mod get_v2_bot_group_summary;
 pub mod get_v2_bot_info;
+pub mod get_v2_bot_insight_demographic;
+pub mod get_v2_bot_insight_followers;
+pub mod get_v2_bot_insight_message_delivery;
+pub mod get_v2_bot_insight_message_event;
 pub mod get_v2_bot_insight_message_event_aggregation;
 pub mod get_v2_bot_message_aggregation_info;
 pub mod get_v2_bot_message_aggregation_list;
+pub mod get_v2_bot_message_quota;
+pub mod get_v2_bot_message_quota_consumption;
+#[deprecated(since = "0.14.0", note = "use get_v2_bot_message_quota")]
 pub mod get_v2_bot_message_quote;
+#[deprecated(since = "0.14.0", note = "use get_v2_bot_message_quota_consumption")]
 pub mod get_v2_bot_message_quote_consumption;
 pub mod get_v2_bot_profile;
+pub mod get_v2_bot_room_member;
+pub mod get_v2_bot_room_members_count;
+pub mod get_v2_bot_room_members_ids;
+pub mod insight;
+pub mod message;
+pub mod outbox;
+pub mod post_v2_bot_audience_group_click;
+pub mod post_v2_bot_audience_group_imp;
+pub mod post_v2_bot_audience_group_upload;
+pub mod post_v2_bot_audience_group_upload_by_file;
+pub mod post_v2_bot_chat_loading_start;
+pub mod post_v2_bot_chat_mark_as_read;
+pub mod post_v2_bot_group_leave;
+pub mod post_v2_bot_message_mark_as_read;
+pub mod post_v2_bot_message_multicast;
 pub mod post_v2_bot_message_push;
+pub mod post_v2_bot_message_validate_broadcast;
+pub mod post_v2_bot_message_validate_multicast;
+pub mod post_v2_bot_message_validate_narrowcast;
 pub mod post_v2_bot_message_validate_push;
+pub mod post_v2_bot_message_validate_reply;
+pub mod post_v2_bot_room_leave;
+pub mod put_v2_bot_audience_group_activate;
+pub mod put_v2_bot_audience_group_update_description;
+pub mod put_v2_bot_audience_group_upload;
+pub mod put_v2_bot_audience_group_upload_by_file;
+pub mod quota_guard;
+pub mod validate;
@@ -0,0 +1,242 @@
+//! 今月使用したすべてのユニットについて、ユニット単位の統計を 1 つの表にまとめるレポート。
+//!
+//! [`get_v2_bot_message_aggregation_list::execute_stream`] でユニット名を全件取得し、
+//! ユニットごとに [`get_v2_bot_insight_message_event_aggregation::execute`] を同時実行数を
+//! 絞って呼ぶ。CTR や動画の再生率は取得した値からローカルで計算する(パーセント)。
+
+use chrono::{Datelike, NaiveDate};
+use futures_util::{StreamExt, TryStreamExt, stream};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    LineOptions,
+    error::Error,
+    messaging_api::{
+        get_v2_bot_insight_message_event_aggregation::{self, ResponseBody},
+        get_v2_bot_message_aggregation_list,
+        insight::{jst_today, validate_range},
+    },
+};
+
+/// 1 ユニット分の行。各カウンターは LINE が返さなかった(閾値未満など)場合 `None`。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitReportRow {
+    pub unit: String,
+    pub impression: Option<u64>,
+    pub unique_impression: Option<u64>,
+    pub click: Option<u64>,
+    pub unique_click: Option<u64>,
+    pub media_played: Option<u64>,
+    pub media_played_25_percent: Option<u64>,
+    pub media_played_50_percent: Option<u64>,
+    pub media_played_75_percent: Option<u64>,
+    pub media_played_100_percent: Option<u64>,
+    pub unique_media_played: Option<u64>,
+    pub unique_media_played_100_percent: Option<u64>,
+    /// `unique_click / unique_impression`
+    pub click_through_rate: Option<f64>,
+    /// `media_played_xx_percent / media_played`
+    pub media_played_25_percent_rate: Option<f64>,
+    pub media_played_50_percent_rate: Option<f64>,
+    pub media_played_75_percent_rate: Option<f64>,
+    pub media_played_100_percent_rate: Option<f64>,
+    /// `unique_media_played_100_percent / unique_media_played`
+    pub unique_completion_rate: Option<f64>,
+}
+
+impl UnitReportRow {
+    pub fn new(unit: &str, response: &ResponseBody) -> Self {
+        let overview = &response.overview;
+        let messages = &response.messages;
+        let media_played = sum(messages.iter().map(|it| it.media_played));
+        let media_played_25_percent = sum(messages.iter().map(|it| it.media_played_25_percent));
+        let media_played_50_percent = sum(messages.iter().map(|it| it.media_played_50_percent));
+        let media_played_75_percent = sum(messages.iter().map(|it| it.media_played_75_percent));
+        let media_played_100_percent = sum(messages.iter().map(|it| it.media_played_100_percent));
+        Self {
+            unit: unit.to_owned(),
+            impression: sum(messages.iter().map(|it| it.impression)),
+            unique_impression: overview.unique_impression,
+            click: sum(response.clicks.iter().map(|it| it.click)),
+            unique_click: overview.unique_click,
+            media_played,
+            media_played_25_percent,
+            media_played_50_percent,
+            media_played_75_percent,
+            media_played_100_percent,
+            unique_media_played: overview.unique_media_played,
+            unique_media_played_100_percent: overview.unique_media_played_100_percent,
+            click_through_rate: rate(overview.unique_click, overview.unique_impression),
+            media_played_25_percent_rate: rate(media_played_25_percent, media_played),
+            media_played_50_percent_rate: rate(media_played_50_percent, media_played),
+            media_played_75_percent_rate: rate(media_played_75_percent, media_played),
+            media_played_100_percent_rate: rate(media_played_100_percent, media_played),
+            unique_completion_rate: rate(
+                overview.unique_media_played_100_percent,
+                overview.unique_media_played,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// ユニット一覧 API が返した順。
+    pub rows: Vec<UnitReportRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregationReportBuilder {
+    from: NaiveDate,
+    to: NaiveDate,
+    concurrency: usize,
+    max_page_count: u64,
+}
+
+impl Default for AggregationReportBuilder {
+    fn default() -> Self {
+        // ユニット一覧は今月分なので、日本時間の今月 1 日から今日までを対象にする
+        let to = jst_today();
+        let from = to.with_day(1).unwrap_or(to);
+        Self {
+            from,
+            to,
+            concurrency: 4,
+            max_page_count: 100,
+        }
+    }
+}
+
+impl AggregationReportBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_range(mut self, from: NaiveDate, to: NaiveDate) -> Result<Self, Box<Error>> {
+        validate_range(from, to)?;
+        self.from = from;
+        self.to = to;
+        Ok(self)
+    }
+
+    /// 同時に統計を取得するユニット数(最小 1)。
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// ユニット一覧を巡回する最大ページ数。
+    pub fn with_max_page_count(mut self, max_page_count: u64) -> Self {
+        self.max_page_count = max_page_count;
+        self
+    }
+
+    pub async fn execute(
+        &self,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> Result<AggregationReport, Box<Error>> {
+        let units = get_v2_bot_message_aggregation_list::execute_stream(
+            channel_access_token,
+            options,
+            self.max_page_count,
+        )
+        .await?;
+
+        let rows = stream::iter(units)
+            .map(|unit| async move {
+                let query_params =
+                    get_v2_bot_insight_message_event_aggregation::QueryParams::with_range(
+                        &unit, self.from, self.to,
+                    )?;
+                let (response, _) = get_v2_bot_insight_message_event_aggregation::execute(
+                    &query_params,
+                    channel_access_token,
+                    options,
+                )
+                .await?;
+                Ok::<_, Box<Error>>(UnitReportRow::new(&unit, &response))
+            })
+            .buffered(self.concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(AggregationReport {
+            from: self.from,
+            to: self.to,
+            rows,
+        })
+    }
+}
+
+// すべて None なら None
+fn sum(values: impl Iterator<Item = Option<u64>>) -> Option<u64> {
+    values.fold(None, |acc, value| match (acc, value) {
+        (None, None) => None,
+        (acc, value) => Some(acc.unwrap_or(0) + value.unwrap_or(0)),
+    })
+}
+
+fn rate(numerator: Option<u64>, denominator: Option<u64>) -> Option<f64> {
+    match (numerator, denominator) {
+        (Some(numerator), Some(denominator)) if denominator > 0 => {
+            Some(numerator as f64 / denominator as f64 * 100.0)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_unit_report_row_new() {
+        let response: ResponseBody = serde_json::from_value(json!({
+            "overview": {
+                "uniqueImpression": 200,
+                "uniqueClick": 50,
+                "uniqueMediaPlayed": 40,
+                "uniqueMediaPlayed100Percent": 10
+            },
+            "messages": [
+                {"seq": 1, "impression": 300, "mediaPlayed": 100, "mediaPlayed25Percent": 80, "mediaPlayed100Percent": 20},
+                {"seq": 2, "impression": 100, "mediaPlayed": null}
+            ],
+            "clicks": [
+                {"seq": 1, "url": "https://example.com", "click": 60},
+                {"seq": 1, "url": "https://example.com/b", "click": null}
+            ]
+        }))
+        .unwrap();
+        let row = UnitReportRow::new("promotion_a", &response);
+        assert_eq!(row.impression, Some(400));
+        assert_eq!(row.click, Some(60));
+        assert_eq!(row.media_played, Some(100));
+        assert_eq!(row.media_played_50_percent, None);
+        assert_eq!(row.click_through_rate, Some(25.0));
+        assert_eq!(row.media_played_25_percent_rate, Some(80.0));
+        assert_eq!(row.media_played_50_percent_rate, None);
+        assert_eq!(row.media_played_100_percent_rate, Some(20.0));
+        assert_eq!(row.unique_completion_rate, Some(25.0));
+    }
+
+    #[test]
+    fn test_aggregation_report_builder_range() {
+        let builder = AggregationReportBuilder::new();
+        assert_eq!(builder.from.day(), 1);
+        assert!(builder.from <= builder.to);
+        let from = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        assert!(
+            builder
+                .with_range(from, NaiveDate::from_ymd_opt(2024, 9, 15).unwrap())
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! オーディエンス管理 API(`/v2/bot/audienceGroup/...`)で共通に使う型。
+//!
+//! 各エンドポイントのモジュールはこれらの型をレスポンスに使う。
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-audience-group
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum AudienceGroupStatus {
+    #[serde(rename = "IN_PROGRESS")]
+    InProgress,
+    #[serde(rename = "READY")]
+    Ready,
+    #[serde(rename = "FAILED")]
+    Failed,
+    #[serde(rename = "EXPIRED")]
+    Expired,
+    #[serde(rename = "INACTIVE")]
+    Inactive,
+    #[serde(rename = "ACTIVATING")]
+    Activating,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum AudienceGroupType {
+    #[serde(rename = "UPLOAD")]
+    Upload,
+    #[serde(rename = "CLICK")]
+    Click,
+    #[serde(rename = "IMP")]
+    Imp,
+    #[serde(rename = "CHAT_TAG")]
+    ChatTag,
+    #[serde(rename = "FRIEND_PATH")]
+    FriendPath,
+    #[serde(rename = "RESERVATION")]
+    Reservation,
+    #[serde(rename = "APP_EVENT")]
+    AppEvent,
+    #[serde(rename = "VIDEO_VIEW")]
+    VideoView,
+    #[serde(rename = "WEBTRAFFIC")]
+    Webtraffic,
+    #[serde(rename = "IMAGE_CLICK")]
+    ImageClick,
+    #[serde(rename = "RICHMENU_IMP")]
+    RichmenuImp,
+    #[serde(rename = "RICHMENU_CLICK")]
+    RichmenuClick,
+    #[serde(rename = "POP_AD_IMP")]
+    PopAdImp,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum AudienceGroupCreateRoute {
+    #[serde(rename = "OA_MANAGER")]
+    OaManager,
+    #[serde(rename = "MESSAGING_API")]
+    MessagingApi,
+    #[serde(rename = "POINT_AD")]
+    PointAd,
+    #[serde(rename = "AD_MANAGER")]
+    AdManager,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum AudienceGroupPermission {
+    #[serde(rename = "READ")]
+    Read,
+    #[serde(rename = "READ_WRITE")]
+    ReadWrite,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum AudienceGroupJobStatus {
+    #[serde(rename = "QUEUED")]
+    Queued,
+    #[serde(rename = "WORKING")]
+    Working,
+    #[serde(rename = "FINISHED")]
+    Finished,
+    #[serde(rename = "FAILED")]
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AudienceGroup {
+    pub audience_group_id: i64,
+    #[serde(rename = "type")]
+    pub audience_group_type: AudienceGroupType,
+    pub description: String,
+    pub status: AudienceGroupStatus,
+    pub failed_type: Option<String>,
+    pub audience_count: Option<u64>,
+    pub created: i64,
+    pub request_id: Option<String>,
+    pub click_url: Option<String>,
+    pub is_ifa_audience: Option<bool>,
+    pub permission: Option<AudienceGroupPermission>,
+    pub create_route: Option<AudienceGroupCreateRoute>,
+    pub activated: Option<i64>,
+    pub inactivated_timestamp: Option<i64>,
+    pub expire_timestamp: Option<i64>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AudienceGroupJob {
+    pub audience_group_job_id: i64,
+    pub audience_group_id: i64,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub job_type: String,
+    pub job_status: AudienceGroupJobStatus,
+    pub failed_type: Option<String>,
+    pub audience_count: Option<u64>,
+    pub created: i64,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// 共有オーディエンスの所有者(Business Manager で共有元になっているアカウント)。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AudienceGroupOwner {
+    pub service_type: String,
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// JSON アップロード(`post/put_v2_bot_audience_group_upload`)で送るユーザー ID または IFA。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Audience {
+    pub id: String,
+}
+
+impl Audience {
+    pub fn new(id: &str) -> Self {
+        Self { id: id.to_owned() }
+    }
+}
+
+/// オーディエンス作成系エンドポイント共通のレスポンス。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateResponseBody {
+    pub audience_group_id: i64,
+    #[serde(rename = "type")]
+    pub audience_group_type: AudienceGroupType,
+    pub description: String,
+    pub created: i64,
+    pub create_route: Option<AudienceGroupCreateRoute>,
+    pub permission: Option<AudienceGroupPermission>,
+    pub expire_timestamp: Option<i64>,
+    pub is_ifa_audience: Option<bool>,
+    pub request_id: Option<String>,
+    pub click_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// `description` は 120 文字以内(LINE の制約)。
+pub(crate) fn validate_description(description: &str) -> Result<(), Box<crate::error::Error>> {
+    crate::error::check_length("description", description.chars().count(), 1, 120)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_audience_group_deserialize() {
+        let group: AudienceGroup = serde_json::from_value(json!({
+            "audienceGroupId": 4389303728991i64,
+            "type": "CLICK",
+            "description": "audienceGroupName_01",
+            "status": "READY",
+            "audienceCount": 2,
+            "created": 1608619802,
+            "requestId": "bb9744f9-47fa-4a29-941e-1234567890ab",
+            "clickUrl": "https://developers.line.biz/",
+            "permission": "READ_WRITE",
+            "createRoute": "MESSAGING_API"
+        }))
+        .unwrap();
+        assert_eq!(group.audience_group_type, AudienceGroupType::Click);
+        assert_eq!(group.status, AudienceGroupStatus::Ready);
+        assert_eq!(group.status.to_string(), "Ready");
+        assert_eq!(serde_json::to_value(&group.status).unwrap(), json!("READY"));
+        assert_eq!(group.audience_count, Some(2));
+    }
+
+    #[test]
+    fn test_validate_description() {
+        assert!(validate_description("a").is_ok());
+        assert!(validate_description(&"あ".repeat(120)).is_ok());
+        assert!(validate_description("").is_err());
+        assert!(validate_description(&"あ".repeat(121)).is_err());
+    }
+}
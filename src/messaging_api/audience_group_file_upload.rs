@@ -0,0 +1,343 @@
+//! 大量のユーザー ID をファイルアップロード(データ用ホスト)でオーディエンスに登録するヘルパー。
+//!
+//! ID は `AsyncRead`(改行区切り)またはイテレーターから逐次読み込み、`chunk_size` 件ごとに
+//! 1 リクエストへ分割する。最初のチャンクで
+//! [`post_v2_bot_audience_group_upload_by_file`] によりオーディエンスを作成し、以降のチャンクは
+//! [`put_v2_bot_audience_group_upload_by_file`] で追加する。[`FileUpload::to_existing`] で
+//! 作成済みのオーディエンスを指定した場合は、すべてのチャンクが追加になる。
+//!
+//! リトライ時に multipart のボディを作り直す必要があるため、1 チャンク分の ID はメモリに保持する。
+//! 既定の `chunk_size` は [`DEFAULT_CHUNK_SIZE`] 件。[`FileUpload::with_chunk_size`] で上限の
+//! [`MAX_AUDIENCES`](150 万件)まで増やせるが、その分(ID 1 件あたり数十バイト、最大で数十 MB)
+//! をメモリに抱えることになる。リクエスト数を減らしたいときだけ増やす。
+//!
+//! 途中のチャンクが失敗した場合はそこで打ち切り、[`UploadReport`] に各チャンクの結果を返す。
+//! [`UploadReport::uploaded_count`] 件は登録済みなので、続きから再開できる。
+
+use futures_util::{Stream, StreamExt, pin_mut, stream};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use async_stream::try_stream;
+
+use crate::{
+    LineOptions, LineResponseHeader,
+    error::{Constraint, Error, ValidationError, check_range},
+    messaging_api::{
+        audience_group::{CreateResponseBody, validate_description},
+        post_v2_bot_audience_group_upload_by_file::{self, MAX_AUDIENCES},
+        put_v2_bot_audience_group_upload_by_file,
+    },
+};
+
+/// 既定の 1 リクエストあたりの件数。
+pub const DEFAULT_CHUNK_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct FileUpload {
+    description: Option<String>,
+    audience_group_id: Option<i64>,
+    is_ifa_audience: Option<bool>,
+    upload_description: Option<String>,
+    chunk_size: usize,
+}
+
+/// 1 チャンク(1 リクエスト)分の結果。
+#[derive(Debug)]
+pub struct ChunkReport {
+    pub index: usize,
+    pub audience_count: usize,
+    pub result: Result<LineResponseHeader, Box<Error>>,
+}
+
+#[derive(Debug, Default)]
+pub struct UploadReport {
+    /// 作成または追加先のオーディエンス ID。作成前に失敗した場合は `None`。
+    pub audience_group_id: Option<i64>,
+    /// 最初のチャンクで作成した場合のレスポンス。
+    pub created: Option<CreateResponseBody>,
+    pub chunks: Vec<ChunkReport>,
+}
+
+impl UploadReport {
+    pub fn is_success(&self) -> bool {
+        self.chunks.iter().all(|chunk| chunk.result.is_ok())
+    }
+
+    /// 登録に成功した ID の件数。
+    pub fn uploaded_count(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.result.is_ok())
+            .map(|chunk| chunk.audience_count)
+            .sum()
+    }
+
+    /// 失敗したチャンクのエラー(打ち切るので高々 1 件)。
+    pub fn error(&self) -> Option<&Error> {
+        self.chunks
+            .iter()
+            .find_map(|chunk| chunk.result.as_ref().err().map(|err| err.as_ref()))
+    }
+}
+
+impl FileUpload {
+    /// 新しいオーディエンスを作成してアップロードする。
+    pub fn new(description: &str) -> Result<Self, Box<Error>> {
+        validate_description(description)?;
+        Ok(Self {
+            description: Some(description.to_string()),
+            audience_group_id: None,
+            is_ifa_audience: None,
+            upload_description: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        })
+    }
+
+    /// 作成済みのオーディエンスに追加する。
+    pub fn to_existing(audience_group_id: i64) -> Self {
+        Self {
+            description: None,
+            audience_group_id: Some(audience_group_id),
+            is_ifa_audience: None,
+            upload_description: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    pub fn with_is_ifa_audience(mut self, is_ifa_audience: bool) -> Self {
+        self.is_ifa_audience = Some(is_ifa_audience);
+        self
+    }
+
+    pub fn with_upload_description(mut self, upload_description: &str) -> Self {
+        self.upload_description = Some(upload_description.to_string());
+        self
+    }
+
+    /// 1 リクエストあたりの件数。1〜[`MAX_AUDIENCES`] の範囲で指定する(既定は [`DEFAULT_CHUNK_SIZE`])。
+    ///
+    /// 1 チャンク分の ID はメモリに保持するため、大きくするほどメモリ使用量が増える。
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Result<Self, Box<Error>> {
+        check_range(
+            "chunk_size",
+            chunk_size as i64,
+            1,
+            MAX_AUDIENCES as i64,
+            None,
+        )?;
+        self.chunk_size = chunk_size;
+        Ok(self)
+    }
+
+    pub async fn execute_from_iter<I, S>(
+        &self,
+        audiences: I,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> UploadReport
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let audiences = stream::iter(audiences.into_iter().map(|it| Ok(it.into())));
+        self.execute_stream(audiences, channel_access_token, options)
+            .await
+    }
+
+    /// 改行区切りの ID を読み込んでアップロードする。空行は読み飛ばす。
+    pub async fn execute_from_reader<R>(
+        &self,
+        reader: R,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> UploadReport
+    where
+        R: AsyncRead + Unpin,
+    {
+        let audiences = try_stream! {
+            let mut lines = BufReader::new(reader).lines();
+            while let Some(line) = lines.next_line().await.map_err(|err| Box::new(Error::Io(err)))? {
+                yield line;
+            }
+        };
+        self.execute_stream(audiences, channel_access_token, options)
+            .await
+    }
+
+    /// ID を `chunk_size` 件ずつアップロードする。空の ID は読み飛ばす。
+    ///
+    /// 新しいオーディエンスを作成する場合に ID が 1 件もなければ、何も送らずに
+    /// `audiences` の `Error::Validation` を 1 チャンク目の結果として返す
+    /// (LINE は空のファイルでの作成を受け付けない)。作成済みのオーディエンスへの追加なら
+    /// 何もせずに成功する。
+    pub async fn execute_stream<S>(
+        &self,
+        audiences: S,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> UploadReport
+    where
+        S: Stream<Item = Result<String, Box<Error>>>,
+    {
+        pin_mut!(audiences); // おまじない
+        let mut report = UploadReport {
+            audience_group_id: self.audience_group_id,
+            ..Default::default()
+        };
+        let mut chunk = vec![];
+        loop {
+            let finished = match audiences.next().await {
+                Some(Ok(id)) => {
+                    let id = id.trim();
+                    if !id.is_empty() {
+                        chunk.push(id.to_string());
+                    }
+                    false
+                }
+                Some(Err(err)) => {
+                    // 読み込みに失敗したチャンクは送らない
+                    report.chunks.push(ChunkReport {
+                        index: report.chunks.len(),
+                        audience_count: chunk.len(),
+                        result: Err(err),
+                    });
+                    return report;
+                }
+                None => true,
+            };
+
+            if chunk.len() >= self.chunk_size || (finished && !chunk.is_empty()) {
+                let audiences = std::mem::take(&mut chunk);
+                let audience_count = audiences.len();
+                let result = self
+                    .send_chunk(&mut report, audiences, channel_access_token, options)
+                    .await;
+                let is_error = result.is_err();
+                report.chunks.push(ChunkReport {
+                    index: report.chunks.len(),
+                    audience_count,
+                    result,
+                });
+                if is_error {
+                    return report;
+                }
+            }
+
+            if finished {
+                if report.audience_group_id.is_none() && report.chunks.is_empty() {
+                    report.chunks.push(ChunkReport {
+                        index: 0,
+                        audience_count: 0,
+                        result: Err(ValidationError::new(
+                            "audiences",
+                            Constraint::Length {
+                                min: 1,
+                                max: MAX_AUDIENCES,
+                            },
+                            0,
+                        )
+                        .into()),
+                    });
+                }
+                return report;
+            }
+        }
+    }
+
+    async fn send_chunk(
+        &self,
+        report: &mut UploadReport,
+        audiences: Vec<String>,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> Result<LineResponseHeader, Box<Error>> {
+        match (report.audience_group_id, &self.description) {
+            (Some(audience_group_id), _) => {
+                let mut body = put_v2_bot_audience_group_upload_by_file::RequestBody::new(
+                    audience_group_id,
+                    audiences,
+                )?;
+                body.upload_description = self.upload_description.clone();
+                put_v2_bot_audience_group_upload_by_file::execute(
+                    body,
+                    channel_access_token,
+                    options,
+                )
+                .await
+                .map(|(_, header)| header)
+            }
+            (None, Some(description)) => {
+                let mut body = post_v2_bot_audience_group_upload_by_file::RequestBody::new(
+                    description,
+                    audiences,
+                )?;
+                body.is_ifa_audience = self.is_ifa_audience;
+                body.upload_description = self.upload_description.clone();
+                let (response, header) = post_v2_bot_audience_group_upload_by_file::execute(
+                    body,
+                    channel_access_token,
+                    options,
+                )
+                .await?;
+                report.audience_group_id = Some(response.audience_group_id);
+                report.created = Some(response);
+                Ok(header)
+            }
+            // new / to_existing のどちらかで必ずどちらかが設定される
+            (None, None) => Err(Box::new(Error::Invalid(
+                "description or audience_group_id is required".to_string(),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_upload_chunk_size() {
+        let upload = FileUpload::new("test audience").unwrap();
+        assert_eq!(upload.chunk_size, DEFAULT_CHUNK_SIZE);
+        assert_eq!(FileUpload::to_existing(1).chunk_size, DEFAULT_CHUNK_SIZE);
+        assert!(upload.clone().with_chunk_size(0).is_err());
+        assert!(upload.clone().with_chunk_size(MAX_AUDIENCES + 1).is_err());
+        assert_eq!(
+            upload
+                .clone()
+                .with_chunk_size(MAX_AUDIENCES)
+                .unwrap()
+                .chunk_size,
+            MAX_AUDIENCES
+        );
+        assert_eq!(upload.with_chunk_size(2).unwrap().chunk_size, 2);
+        assert!(FileUpload::new("").is_err());
+        assert!(
+            post_v2_bot_audience_group_upload_by_file::RequestBody::new("test audience", vec![])
+                .is_err()
+        );
+    }
+
+    // 作成時に ID が 1 件もなければ、送信せずに検証エラーを返す
+    #[tokio::test]
+    async fn test_file_upload_empty_input() {
+        let options = LineOptions::builder()
+            .with_data_prefix_url("http://127.0.0.1:1")
+            .build();
+        let report = FileUpload::new("test audience")
+            .unwrap()
+            .execute_from_iter(["", " "], "test_channel_access_token", &options)
+            .await;
+        assert!(!report.is_success());
+        assert_eq!(report.audience_group_id, None);
+        assert_eq!(report.chunks.len(), 1);
+        assert!(matches!(report.error(), Some(Error::Validation(_))));
+
+        // 追加なら何もしない
+        let report = FileUpload::to_existing(1234567890123)
+            .execute_from_iter(Vec::<String>::new(), "test_channel_access_token", &options)
+            .await;
+        assert!(report.is_success());
+        assert!(report.chunks.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! Webhook イベントの `source`(送信元)を表す型と、送信元ごとに適切なエンドポイントを
+//! 呼び分けるヘルパー。
+//!
+//! `source.type` が `user` / `group` / `room` のいずれかによって、メンバー数・メンバー ID・
+//! プロフィール・退出の各操作で使うエンドポイントが異なる。[`ChatSource`] は Webhook の
+//! `source` オブジェクトをそのままデシリアライズでき、各メソッドが対応するモジュールの
+//! `execute` を呼ぶ。1:1 トーク(`user`)に存在しない操作は `Error::Invalid` を返す。
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    LineOptions, LineResponseHeader,
+    error::Error,
+    messaging_api::{
+        get_v2_bot_group_member, get_v2_bot_group_members_count, get_v2_bot_group_members_ids,
+        get_v2_bot_profile, get_v2_bot_room_member, get_v2_bot_room_members_count,
+        get_v2_bot_room_members_ids, post_v2_bot_group_leave, post_v2_bot_room_leave,
+    },
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#source-user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ChatSource {
+    #[serde(rename_all = "camelCase")]
+    User { user_id: String },
+    #[serde(rename_all = "camelCase")]
+    Group {
+        group_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user_id: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Room {
+        room_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user_id: Option<String>,
+    },
+}
+
+/// 送信元によらず共通のメンバープロフィール。
+///
+/// `language` / `status_message` はユーザープロフィール(1:1 トーク)でのみ取得でき、
+/// グループ・トークルームのメンバーでは `None`。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberProfile {
+    pub display_name: String,
+    pub user_id: String,
+    pub picture_url: Option<String>,
+    pub language: Option<String>,
+    pub status_message: Option<String>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+// グループ・トークルームのメンバープロフィールは同じ形なので、共通型へ寄せる
+macro_rules! impl_from_member_profile {
+    ($($module:ident),*) => {
+        $(
+            impl From<$module::ResponseBody> for MemberProfile {
+                fn from(response: $module::ResponseBody) -> Self {
+                    Self {
+                        display_name: response.display_name,
+                        user_id: response.user_id,
+                        picture_url: response.picture_url,
+                        language: None,
+                        status_message: None,
+                        extra: response.extra,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_member_profile!(get_v2_bot_group_member, get_v2_bot_room_member);
+
+impl From<get_v2_bot_profile::ResponseBody> for MemberProfile {
+    fn from(response: get_v2_bot_profile::ResponseBody) -> Self {
+        Self {
+            display_name: response.display_name,
+            user_id: response.user_id,
+            picture_url: response.picture_url,
+            language: response.language,
+            status_message: response.status_message,
+            extra: response.extra,
+        }
+    }
+}
+
+impl ChatSource {
+    /// push の `to` やローディングアニメーションの `chatId` に渡す ID。
+    pub fn chat_id(&self) -> &str {
+        match self {
+            ChatSource::User { user_id } => user_id,
+            ChatSource::Group { group_id, .. } => group_id,
+            ChatSource::Room { room_id, .. } => room_id,
+        }
+    }
+
+    /// イベントを発生させたユーザーの ID(グループ・トークルームでは取得できない場合がある)。
+    pub fn user_id(&self) -> Option<&str> {
+        match self {
+            ChatSource::User { user_id } => Some(user_id),
+            ChatSource::Group { user_id, .. } | ChatSource::Room { user_id, .. } => {
+                user_id.as_deref()
+            }
+        }
+    }
+
+    pub async fn execute_members_count(
+        &self,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> Result<(u64, LineResponseHeader), Box<Error>> {
+        match self {
+            ChatSource::User { .. } => Err(Box::new(Error::Invalid(
+                "members count is not available for user source".to_string(),
+            ))),
+            ChatSource::Group { group_id, .. } => {
+                get_v2_bot_group_members_count::execute(group_id, channel_access_token, options)
+                    .await
+                    .map(|(response, header)| (response.count, header))
+            }
+            ChatSource::Room { room_id, .. } => {
+                get_v2_bot_room_members_count::execute(room_id, channel_access_token, options)
+                    .await
+                    .map(|(response, header)| (response.count, header))
+            }
+        }
+    }
+
+    /// メンバーのユーザー ID を全ページ取得する。1:1 トークではそのユーザー 1 人を返す。
+    pub async fn execute_members_ids(
+        &self,
+        channel_access_token: &str,
+        options: &LineOptions,
+        max_page_count: u64,
+    ) -> Result<Vec<String>, Box<Error>> {
+        match self {
+            ChatSource::User { user_id } => Ok(vec![user_id.clone()]),
+            ChatSource::Group { group_id, .. } => {
+                get_v2_bot_group_members_ids::execute_stream(
+                    group_id,
+                    channel_access_token,
+                    options,
+                    max_page_count,
+                )
+                .await
+            }
+            ChatSource::Room { room_id, .. } => {
+                get_v2_bot_room_members_ids::execute_stream(
+                    room_id,
+                    channel_access_token,
+                    options,
+                    max_page_count,
+                )
+                .await
+            }
+        }
+    }
+
+    /// 送信元に応じて、グループ・トークルームのメンバープロフィールまたはユーザープロフィールを取得する。
+    pub async fn execute_member_profile(
+        &self,
+        user_id: &str,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> Result<(MemberProfile, LineResponseHeader), Box<Error>> {
+        match self {
+            ChatSource::User { .. } => {
+                get_v2_bot_profile::execute(user_id, channel_access_token, options)
+                    .await
+                    .map(|(response, header)| (response.into(), header))
+            }
+            ChatSource::Group { group_id, .. } => {
+                get_v2_bot_group_member::execute(group_id, user_id, channel_access_token, options)
+                    .await
+                    .map(|(response, header)| (response.into(), header))
+            }
+            ChatSource::Room { room_id, .. } => {
+                get_v2_bot_room_member::execute(room_id, user_id, channel_access_token, options)
+                    .await
+                    .map(|(response, header)| (response.into(), header))
+            }
+        }
+    }
+
+    pub async fn execute_leave(
+        &self,
+        channel_access_token: &str,
+        options: &LineOptions,
+    ) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+        match self {
+            ChatSource::User { .. } => Err(Box::new(Error::Invalid(
+                "leave is not available for user source".to_string(),
+            ))),
+            ChatSource::Group { group_id, .. } => {
+                post_v2_bot_group_leave::execute(group_id, channel_access_token, options).await
+            }
+            ChatSource::Room { room_id, .. } => {
+                post_v2_bot_room_leave::execute(room_id, channel_access_token, options).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // ユーザープロフィールの language / statusMessage も落とさない
+    #[test]
+    fn test_member_profile_from_user_profile() {
+        let response: get_v2_bot_profile::ResponseBody = serde_json::from_value(json!({
+            "displayName": "LINE taro",
+            "userId": "U123",
+            "language": "ja",
+            "pictureUrl": "https://profile.line-scdn.net/abcdefghijklmn",
+            "statusMessage": "Hello, LINE!"
+        }))
+        .unwrap();
+        let profile: MemberProfile = response.into();
+        assert_eq!(profile.display_name, "LINE taro");
+        assert_eq!(profile.language.as_deref(), Some("ja"));
+        assert_eq!(profile.status_message.as_deref(), Some("Hello, LINE!"));
+        assert!(profile.extra.is_empty());
+
+        let response: get_v2_bot_group_member::ResponseBody = serde_json::from_value(json!({
+            "displayName": "LINE taro",
+            "userId": "U123"
+        }))
+        .unwrap();
+        let profile: MemberProfile = response.into();
+        assert_eq!(profile.language, None);
+        assert_eq!(profile.status_message, None);
+    }
+
+    #[test]
+    fn test_chat_source_from_webhook_source() {
+        let source: ChatSource = serde_json::from_value(json!({
+            "type": "room",
+            "roomId": "R123",
+            "userId": "U123"
+        }))
+        .unwrap();
+        assert_eq!(
+            source,
+            ChatSource::Room {
+                room_id: "R123".to_string(),
+                user_id: Some("U123".to_string())
+            }
+        );
+        assert_eq!(source.chat_id(), "R123");
+        assert_eq!(source.user_id(), Some("U123"));
+
+        let source: ChatSource =
+            serde_json::from_value(json!({"type": "group", "groupId": "C123"})).unwrap();
+        assert_eq!(source.chat_id(), "C123");
+        assert_eq!(source.user_id(), None);
+
+        let source: ChatSource =
+            serde_json::from_value(json!({"type": "user", "userId": "U999"})).unwrap();
+        assert_eq!(source.chat_id(), "U999");
+        assert_eq!(source.user_id(), Some("U999"));
+    }
+}
@@ -0,0 +1,173 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::Error,
+    execute_api, make_url,
+    messaging_api::audience_group::{AudienceGroup, AudienceGroupCreateRoute, AudienceGroupStatus},
+};
+
+use async_stream::try_stream;
+use futures_util::{Stream, pin_mut, stream::TryStreamExt};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-audience-groups
+const URL: &str = "/v2/bot/audienceGroup/list";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_audience_group_list", "GET", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    pub page: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<AudienceGroupStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub includes_external_public_groups: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_route: Option<AudienceGroupCreateRoute>,
+}
+
+impl QueryParams {
+    pub fn new() -> Self {
+        Self {
+            page: 1,
+            // 最大値の 40 件ずつ取得する
+            size: Some(40),
+            description: None,
+            status: None,
+            includes_external_public_groups: None,
+            create_route: None,
+        }
+    }
+}
+
+impl Default for QueryParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    #[serde(default)]
+    pub audience_groups: Vec<AudienceGroup>,
+    pub has_next_page: bool,
+    pub total_count: u64,
+    pub read_write_audience_group_total_count: Option<u64>,
+    pub page: u64,
+    pub size: u64,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(
+    query_params: &QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url).query(query_params);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    query_params: &QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(query_params, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(query_params),
+    )
+    .await
+}
+
+/// `query_params.page` から順にページを巡回し、オーディエンスを 1 件ずつ流すストリームを返す。
+///
+/// ページ番号方式なので `hasNextPage` が `false` になるまで `page` を 1 ずつ進める。
+pub fn make_stream(
+    query_params: QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+    max_page_count: u64,
+) -> impl Stream<Item = Result<AudienceGroup, Box<Error>>> {
+    try_stream! {
+        let mut current_page_count = 0;
+        let mut query_params = query_params;
+        loop {
+            // 通常のAPI呼び出し
+            let (result, _) = execute(&query_params, channel_access_token, options).await?;
+
+            // 空なら終了
+            if result.audience_groups.is_empty() {
+                break;
+            }
+
+            // データがあったら1件づつ返す
+            for item in result.audience_groups {
+                yield item;
+            }
+
+            // 次のページがない場合は終了
+            if !result.has_next_page {
+                break;
+            }
+
+            // 次のページに進む
+            query_params.page += 1;
+
+            // 最大ページ数を超えたら終了
+            current_page_count += 1;
+            if current_page_count > max_page_count {
+                break;
+            }
+        }
+    }
+}
+
+pub async fn execute_stream(
+    query_params: QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+    max_page_count: u64,
+) -> Result<Vec<AudienceGroup>, Box<Error>> {
+    let stream = self::make_stream(query_params, channel_access_token, options, max_page_count);
+    pin_mut!(stream); // おまじない
+    let mut result = vec![];
+    while let Some(item) = stream.try_next().await? {
+        result.push(item);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_audience_group_list -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_audience_group_list() {
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let res = super::execute_stream(
+            super::QueryParams::new(),
+            &channel_access_token,
+            &options,
+            100,
+        )
+        .await
+        .unwrap();
+        println!("res: {res:?}");
+    }
+}
@@ -0,0 +1,74 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::Error,
+    execute_api, make_url,
+    messaging_api::audience_group::{AudienceGroup, AudienceGroupJob, AudienceGroupOwner},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-shared-audience
+const URL: &str = "/v2/bot/audienceGroup/shared";
+const ENDPOINT: Endpoint = Endpoint::new(
+    "get_v2_bot_audience_group_shared",
+    "GET",
+    "/v2/bot/audienceGroup/shared/{audienceGroupId}",
+);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    pub audience_group: AudienceGroup,
+    #[serde(default)]
+    pub jobs: Vec<AudienceGroupJob>,
+    pub owner: Option<AudienceGroupOwner>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(
+    audience_group_id: i64,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(&format!("{URL}/{audience_group_id}"), options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    audience_group_id: i64,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(audience_group_id, channel_access_token, options),
+        options,
+        None,
+        || serde_json::Value::Null,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // AUDIENCE_GROUP_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_audience_group_shared -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_audience_group_shared() {
+        let audience_group_id: i64 = std::env::var("AUDIENCE_GROUP_ID").unwrap().parse().unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let (response, header) = super::execute(audience_group_id, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -0,0 +1,75 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-group-member-profile
+const URL: &str = "/v2/bot/group";
+const ENDPOINT: Endpoint = Endpoint::new(
+    "get_v2_bot_group_member",
+    "GET",
+    "/v2/bot/group/{groupId}/member/{userId}",
+);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    pub display_name: String,
+    pub user_id: String,
+    pub picture_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(
+    group_id: &str,
+    user_id: &str,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(&format!("{URL}/{group_id}/member/{user_id}"), options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    group_id: &str,
+    user_id: &str,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(group_id, user_id, channel_access_token, options),
+        options,
+        None,
+        || serde_json::Value::Null,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // GROUP_ID=xxx USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_group_member -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_group_member() {
+        let group_id = std::env::var("GROUP_ID").unwrap();
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let (response, header) =
+            super::execute(&group_id, &user_id, &channel_access_token, &options)
+                .await
+                .unwrap();
+        println!("{}", serde_json::to_value(response).unwrap());
+        println!("{header:?}");
+    }
+}
@@ -3,12 +3,13 @@ use serde::{Deserialize, Serialize};
 use strum::Display;
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/messaging-api/#get-bot-info
 const URL: &str = "/v2/bot/info";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_info", "GET", URL);
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
 pub enum ChatMode {
@@ -54,9 +55,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(channel_access_token, options),
         options,
-        is_standard_retry,
         None,
         || serde_json::Value::Null,
     )
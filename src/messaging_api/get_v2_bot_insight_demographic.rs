@@ -0,0 +1,105 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-demographic
+const URL: &str = "/v2/bot/insight/demographic";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_insight_demographic", "GET", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GenderTile {
+    pub gender: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgeTile {
+    pub age: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AreaTile {
+    pub area: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTypeTile {
+    pub app_type: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionPeriodTile {
+    pub subscription_period: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    // 友だちが 20 人未満などで統計が出せない場合は false で、各配列は空になる
+    pub available: bool,
+    #[serde(default)]
+    pub genders: Vec<GenderTile>,
+    #[serde(default)]
+    pub ages: Vec<AgeTile>,
+    #[serde(default)]
+    pub areas: Vec<AreaTile>,
+    #[serde(default)]
+    pub app_types: Vec<AppTypeTile>,
+    #[serde(default)]
+    pub subscription_periods: Vec<SubscriptionPeriodTile>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(channel_access_token: &str, options: &LineOptions) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(channel_access_token, options),
+        options,
+        None,
+        || serde_json::Value::Null,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_insight_demographic -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_insight_demographic() {
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let (response, header) = super::execute(&channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
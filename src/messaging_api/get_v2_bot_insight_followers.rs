@@ -0,0 +1,91 @@
+use chrono::NaiveDate;
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::Error,
+    execute_api, make_url,
+    messaging_api::insight::{InsightStatus, jst_today},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-number-of-followers
+const URL: &str = "/v2/bot/insight/followers";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_insight_followers", "GET", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QueryParams {
+    // 日本時間の日付。送信時は yyyyMMdd
+    #[serde(with = "crate::messaging_api::insight::yyyymmdd")]
+    pub date: NaiveDate,
+}
+
+impl QueryParams {
+    pub fn new(date: NaiveDate) -> Self {
+        Self { date }
+    }
+
+    /// 日本時間の昨日(当日分は集計されていない)。
+    pub fn yesterday() -> Self {
+        Self::new(jst_today() - chrono::Duration::days(1))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    pub status: InsightStatus,
+    pub followers: Option<u64>,
+    pub targeted_reaches: Option<u64>,
+    pub blocks: Option<u64>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(
+    query_params: &QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url).query(query_params);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    query_params: &QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(query_params, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(query_params),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // DATE=20240801 CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_insight_followers -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_insight_followers() {
+        let date =
+            chrono::NaiveDate::parse_from_str(&std::env::var("DATE").unwrap(), "%Y%m%d").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let query_params = super::QueryParams::new(date);
+        let (response, header) = super::execute(&query_params, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
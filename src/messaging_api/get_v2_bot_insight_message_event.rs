@@ -0,0 +1,102 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
+};
+
+// メッセージ・クリックの各項目はユニット単位の統計と同じ形
+pub use crate::messaging_api::get_v2_bot_insight_message_event_aggregation::{Click, Message};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-message-event
+const URL: &str = "/v2/bot/insight/message/event";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_insight_message_event", "GET", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    // 送信時のレスポンスヘッダー x-line-request-id の値
+    pub request_id: String,
+}
+
+impl QueryParams {
+    pub fn new(request_id: &str) -> Self {
+        Self {
+            request_id: request_id.to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Overview {
+    pub request_id: String,
+    pub timestamp: i64,
+    pub delivered: Option<u64>,
+    pub unique_impression: Option<u64>,
+    pub unique_click: Option<u64>,
+    pub unique_media_played: Option<u64>,
+    pub unique_media_played_100_percent: Option<u64>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseBody {
+    pub overview: Overview,
+    #[serde(default)]
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub clicks: Vec<Click>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(
+    query_params: &QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url).query(query_params);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    query_params: &QueryParams,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(query_params, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(query_params),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // REQUEST_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_insight_message_event -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_insight_message_event() {
+        let request_id = std::env::var("REQUEST_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let query_params = super::QueryParams::new(&request_id);
+        let (response, header) = super::execute(&query_params, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -2,32 +2,57 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::Error,
+    execute_api, make_url,
+    messaging_api::insight::{MAX_RANGE_DAYS, jst_today, validate_range},
 };
-use chrono::prelude::*;
+use chrono::NaiveDate;
 
 // https://developers.line.biz/ja/reference/messaging-api/#get-statistics-per-unit
 const URL: &str = "/v2/bot/insight/message/event/aggregation";
+const ENDPOINT: Endpoint =
+    Endpoint::new("get_v2_bot_insight_message_event_aggregation", "GET", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
     pub custom_aggregation_unit: String,
-    pub from: String,
-    pub to: String,
+    // 日本時間の日付。送信時は yyyyMMdd
+    #[serde(with = "crate::messaging_api::insight::yyyymmdd")]
+    pub from: NaiveDate,
+    #[serde(with = "crate::messaging_api::insight::yyyymmdd")]
+    pub to: NaiveDate,
 }
 
 impl QueryParams {
+    /// 日本時間の今日までの 30 日間を対象にする。
     pub fn new(custom_aggregation_unit: &str) -> Self {
-        let to = Local::now();
-        let from = to - chrono::Duration::days(30);
+        let to = jst_today();
+        let from = to - chrono::Duration::days(MAX_RANGE_DAYS);
         Self {
             custom_aggregation_unit: custom_aggregation_unit.to_owned(),
-            from: from.format("%Y%m%d").to_string(),
-            to: to.format("%Y%m%d").to_string(),
+            from,
+            to,
         }
     }
+
+    pub fn with_range(
+        custom_aggregation_unit: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Self, Box<Error>> {
+        validate_range(from, to)?;
+        Ok(Self {
+            custom_aggregation_unit: custom_aggregation_unit.to_owned(),
+            from,
+            to,
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), Box<Error>> {
+        validate_range(self.from, self.to)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -101,10 +126,12 @@ pub async fn execute(
     channel_access_token: &str,
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    // フィールドは pub なので、送信前に期間を確認する
+    query_params.validate()?;
     execute_api(
+        ENDPOINT,
         || build(query_params, channel_access_token, options),
         options,
-        is_standard_retry,
         None,
         || crate::serialize_log_body(query_params),
     )
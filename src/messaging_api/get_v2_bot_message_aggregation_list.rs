@@ -4,8 +4,8 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 use async_stream::try_stream;
@@ -13,6 +13,7 @@ use futures_util::{Stream, pin_mut, stream::TryStreamExt};
 
 // https://developers.line.biz/ja/reference/messaging-api/#get-a-list-of-unit-names-assigned-during-this-month
 const URL: &str = "/v2/bot/message/aggregation/list";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_message_aggregation_list", "GET", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct QueryParams {
@@ -63,9 +64,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(query_params, channel_access_token, options),
         options,
-        is_standard_retry,
         None,
         || crate::serialize_log_body(query_params),
     )
@@ -0,0 +1,71 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-quota
+const URL: &str = "/v2/bot/message/quota";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_message_quota", "GET", URL);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum QuotaType {
+    // 上限なし
+    #[serde(rename = "none")]
+    None,
+    // 上限あり(value が上限)
+    #[serde(rename = "limited")]
+    Limited,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseBody {
+    #[serde(rename = "type")]
+    pub quota_type: QuotaType,
+    pub value: Option<i64>,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(channel_access_token: &str, options: &LineOptions) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(channel_access_token, options),
+        options,
+        None,
+        || serde_json::Value::Null,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_message_quota -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_message_quota() {
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let (response, header) = super::execute(&channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
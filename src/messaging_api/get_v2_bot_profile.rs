@@ -2,12 +2,13 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/messaging-api/#get-profile
 const URL: &str = "/v2/bot/profile";
+const ENDPOINT: Endpoint = Endpoint::new("get_v2_bot_profile", "GET", "/v2/bot/profile/{userId}");
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -36,9 +37,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(user_id, channel_access_token, options),
         options,
-        is_standard_retry,
         None,
         || serde_json::Value::Null,
     )
@@ -0,0 +1,64 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#get-members-room-count
+const URL: &str = "/v2/bot/room";
+const ENDPOINT: Endpoint = Endpoint::new(
+    "get_v2_bot_room_members_count",
+    "GET",
+    "/v2/bot/room/{roomId}/members/count",
+);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseBody {
+    pub count: u64,
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(room_id: &str, channel_access_token: &str, options: &LineOptions) -> RequestBuilder {
+    let url = make_url(&format!("{URL}/{room_id}/members/count"), options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.get(&url);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    room_id: &str,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(room_id, channel_access_token, options),
+        options,
+        None,
+        || serde_json::Value::Null,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // ROOM_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_get_v2_bot_room_members_count -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_get_v2_bot_room_members_count() {
+        let room_id = std::env::var("ROOM_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let (response, header) = super::execute(&room_id, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{}", serde_json::to_value(response).unwrap());
+        println!("{header:?}");
+    }
+}
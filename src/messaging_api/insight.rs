@@ -0,0 +1,340 @@
+//! 統計情報 API(`/v2/bot/insight/...`)で共通に使う型と日付の扱い。
+//!
+//! 日付はすべて日本時間(UTC+9)の暦日として [`NaiveDate`] で表し、送信時に `yyyyMMdd` にする。
+//!
+//! [`collect`] は日付ごとの統計情報(友だち数・配信数・ユニット単位の統計)を期間分まとめて取得する。
+
+use std::{collections::BTreeMap, ops::RangeInclusive, time::Duration};
+
+use chrono::{FixedOffset, NaiveDate, Utc};
+use futures_util::{StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    LineOptions,
+    error::{Constraint, Error, ValidationError, check_range},
+    messaging_api::{
+        get_v2_bot_insight_followers, get_v2_bot_insight_message_delivery,
+        get_v2_bot_insight_message_event_aggregation,
+    },
+};
+
+/// 日付指定の統計情報の集計状態。`Ready` 以外のときは各カウンターが `None` になる。
+// https://developers.line.biz/ja/reference/messaging-api/#get-number-of-delivery-messages-response
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone)]
+pub enum InsightStatus {
+    #[serde(rename = "ready")]
+    Ready,
+    #[serde(rename = "unready")]
+    Unready,
+    #[serde(rename = "out_of_service")]
+    OutOfService,
+}
+
+/// 統計情報 API が 1 回で受け付ける期間(`to - from`)の上限日数。
+pub const MAX_RANGE_DAYS: i64 = 30;
+
+/// LINE の統計情報は日本時間(UTC+9)で日付を区切る。
+pub fn jst_offset() -> FixedOffset {
+    // 9 時間は FixedOffset の範囲内なので失敗しない
+    FixedOffset::east_opt(9 * 60 * 60).unwrap()
+}
+
+/// 日本時間での今日の日付。
+pub fn jst_today() -> NaiveDate {
+    Utc::now().with_timezone(&jst_offset()).date_naive()
+}
+
+/// `from <= to` かつ期間が [`MAX_RANGE_DAYS`] 日以内であることを確認する。
+pub fn validate_range(from: NaiveDate, to: NaiveDate) -> Result<(), Box<Error>> {
+    if from > to {
+        return Err(ValidationError::new(
+            "from",
+            Constraint::Before {
+                other: "to".to_string(),
+            },
+            from.to_string(),
+        )
+        .into());
+    }
+    check_range("range", (to - from).num_days(), 0, MAX_RANGE_DAYS, None)?;
+    Ok(())
+}
+
+/// `NaiveDate` を LINE の `yyyyMMdd` 形式で (デ)シリアライズする。`#[serde(with = "...")]` で使う。
+pub(crate) mod yyyymmdd {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%Y%m%d";
+
+    pub fn serialize<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveDate::parse_from_str(&text, FORMAT).map_err(serde::de::Error::custom)
+    }
+}
+
+/// [`collect`] の設定。
+#[derive(Debug, Clone)]
+pub struct CollectOptions {
+    concurrency: usize,
+    units: Vec<String>,
+    unready_retry_count: usize,
+    unready_retry_duration: Duration,
+}
+
+impl Default for CollectOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            units: vec![],
+            unready_retry_count: 3,
+            unready_retry_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+impl CollectOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 同時に取得する日数(最小 1)。
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// ユニット単位の統計も日ごとに取得するユニット名。
+    pub fn with_units(mut self, units: Vec<String>) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// `unready` だった日を取り直す回数。
+    pub fn with_unready_retry_count(mut self, unready_retry_count: usize) -> Self {
+        self.unready_retry_count = unready_retry_count;
+        self
+    }
+
+    /// `unready` だった日を取り直すまでの待ち時間。
+    pub fn with_unready_retry_duration(mut self, unready_retry_duration: Duration) -> Self {
+        self.unready_retry_duration = unready_retry_duration;
+        self
+    }
+}
+
+/// 1 日分の統計情報。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyInsight {
+    pub followers: get_v2_bot_insight_followers::ResponseBody,
+    pub delivery: get_v2_bot_insight_message_delivery::ResponseBody,
+    /// ユニット名ごとの当日分の統計。
+    pub units: BTreeMap<String, get_v2_bot_insight_message_event_aggregation::Overview>,
+}
+
+impl DailyInsight {
+    pub fn is_unready(&self) -> bool {
+        self.followers.status == InsightStatus::Unready
+            || self.delivery.status == InsightStatus::Unready
+    }
+
+    /// CSV などに出力しやすい 1 行の形にする(ユニット単位の統計は [`DailyInsight::unit_rows`])。
+    pub fn to_row(&self, date: NaiveDate) -> DailyInsightRow {
+        DailyInsightRow {
+            date,
+            followers: self.followers.followers,
+            targeted_reaches: self.followers.targeted_reaches,
+            blocks: self.followers.blocks,
+            broadcast: self.delivery.broadcast,
+            targeting: self.delivery.targeting,
+            auto_response: self.delivery.auto_response,
+            welcome_response: self.delivery.welcome_response,
+            chat: self.delivery.chat,
+            api_broadcast: self.delivery.api_broadcast,
+            api_push: self.delivery.api_push,
+            api_multicast: self.delivery.api_multicast,
+            api_narrowcast: self.delivery.api_narrowcast,
+            api_reply: self.delivery.api_reply,
+        }
+    }
+
+    pub fn unit_rows(&self, date: NaiveDate) -> Vec<UnitInsightRow> {
+        self.units
+            .iter()
+            .map(|(unit, overview)| UnitInsightRow {
+                date,
+                unit: unit.clone(),
+                unique_impression: overview.unique_impression,
+                unique_click: overview.unique_click,
+                unique_media_played: overview.unique_media_played,
+                unique_media_played_100_percent: overview.unique_media_played_100_percent,
+            })
+            .collect()
+    }
+}
+
+/// [`DailyInsight`] を平らにした行。ネストがないので CSV にもそのままシリアライズできる。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyInsightRow {
+    pub date: NaiveDate,
+    pub followers: Option<u64>,
+    pub targeted_reaches: Option<u64>,
+    pub blocks: Option<u64>,
+    pub broadcast: Option<u64>,
+    pub targeting: Option<u64>,
+    pub auto_response: Option<u64>,
+    pub welcome_response: Option<u64>,
+    pub chat: Option<u64>,
+    pub api_broadcast: Option<u64>,
+    pub api_push: Option<u64>,
+    pub api_multicast: Option<u64>,
+    pub api_narrowcast: Option<u64>,
+    pub api_reply: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitInsightRow {
+    pub date: NaiveDate,
+    pub unit: String,
+    pub unique_impression: Option<u64>,
+    pub unique_click: Option<u64>,
+    pub unique_media_played: Option<u64>,
+    pub unique_media_played_100_percent: Option<u64>,
+}
+
+/// 期間内の各日(日本時間)の統計情報を取得する。
+///
+/// 日ごとに友だち数・配信数(とユニット単位の統計)を取得し、最大 `concurrency` 日を同時に処理する。
+/// `unready`(集計中)の日は結果に入れず、`unready_retry_duration` 待ってから
+/// `unready_retry_count` 回まで取り直す。それでも `unready` の日は結果に含まれない。
+/// API エラーが起きた場合はその時点でエラーを返す。
+pub async fn collect(
+    range: RangeInclusive<NaiveDate>,
+    channel_access_token: &str,
+    options: &LineOptions,
+    collect_options: &CollectOptions,
+) -> Result<BTreeMap<NaiveDate, DailyInsight>, Box<Error>> {
+    if range.start() > range.end() {
+        return Err(ValidationError::new(
+            "range",
+            Constraint::Before {
+                other: "end".to_string(),
+            },
+            range.start().to_string(),
+        )
+        .into());
+    }
+
+    let mut result = BTreeMap::new();
+    let mut pending: Vec<NaiveDate> = range
+        .start()
+        .iter_days()
+        .take_while(|it| it <= range.end())
+        .collect();
+    let mut retry_count = 0;
+    loop {
+        let responses: Vec<_> = stream::iter(pending.iter().copied())
+            .map(|date| fetch_day(date, channel_access_token, options, collect_options))
+            .buffer_unordered(collect_options.concurrency)
+            .collect()
+            .await;
+
+        let mut unready = vec![];
+        for response in responses {
+            let (date, daily) = response?;
+            match daily {
+                Some(daily) => {
+                    result.insert(date, daily);
+                }
+                None => unready.push(date),
+            }
+        }
+
+        if unready.is_empty() || retry_count >= collect_options.unready_retry_count {
+            break;
+        }
+        unready.sort();
+        pending = unready;
+        retry_count += 1;
+        tokio::time::sleep(collect_options.unready_retry_duration).await;
+    }
+    Ok(result)
+}
+
+// unready の日は None を返す
+async fn fetch_day(
+    date: NaiveDate,
+    channel_access_token: &str,
+    options: &LineOptions,
+    collect_options: &CollectOptions,
+) -> Result<(NaiveDate, Option<DailyInsight>), Box<Error>> {
+    let (followers, _) = get_v2_bot_insight_followers::execute(
+        &get_v2_bot_insight_followers::QueryParams::new(date),
+        channel_access_token,
+        options,
+    )
+    .await?;
+    let (delivery, _) = get_v2_bot_insight_message_delivery::execute(
+        &get_v2_bot_insight_message_delivery::QueryParams::new(date),
+        channel_access_token,
+        options,
+    )
+    .await?;
+    let mut daily = DailyInsight {
+        followers,
+        delivery,
+        units: BTreeMap::new(),
+    };
+    // 集計前ならユニット単位の統計は取りに行かない
+    if daily.is_unready() {
+        return Ok((date, None));
+    }
+
+    for unit in &collect_options.units {
+        let query_params = get_v2_bot_insight_message_event_aggregation::QueryParams::with_range(
+            unit, date, date,
+        )?;
+        let (response, _) = get_v2_bot_insight_message_event_aggregation::execute(
+            &query_params,
+            channel_access_token,
+            options,
+        )
+        .await?;
+        daily.units.insert(unit.clone(), response.overview);
+    }
+    Ok((date, Some(daily)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_range() {
+        let from = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        assert!(validate_range(from, from).is_ok());
+        assert!(validate_range(from, NaiveDate::from_ymd_opt(2024, 8, 31).unwrap()).is_ok());
+        assert!(validate_range(from, NaiveDate::from_ymd_opt(2024, 9, 1).unwrap()).is_err());
+        assert!(validate_range(from, NaiveDate::from_ymd_opt(2024, 7, 31).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_jst_today() {
+        // UTC 15:00 以降は日本時間で翌日になる
+        let utc = chrono::DateTime::parse_from_rfc3339("2024-08-01T15:00:00Z").unwrap();
+        assert_eq!(
+            utc.with_timezone(&jst_offset()).date_naive(),
+            NaiveDate::from_ymd_opt(2024, 8, 2).unwrap()
+        );
+        assert!(jst_today() >= Utc::now().date_naive());
+    }
+}
@@ -0,0 +1,206 @@
+//! 送信用メッセージオブジェクトの型付きモデル。
+//!
+//! 各送信エンドポイントの `RequestBody::messages` は `Vec<serde_json::Value>` のまま
+//! なので、本モジュールの [`Message`] は [`Message::into_value`] で `Value` に変換して渡す。
+//! 型で表現していないメッセージ種別は従来どおり `serde_json::json!` で組み立てればよい。
+
+use serde::{Deserialize, Serialize};
+
+// https://developers.line.biz/ja/reference/messaging-api/#icon-nickname-switch
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sender {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+impl Sender {
+    pub fn new(name: Option<&str>, icon_url: Option<&str>) -> Self {
+        Self {
+            name: name.map(|it| it.to_owned()),
+            icon_url: icon_url.map(|it| it.to_owned()),
+        }
+    }
+}
+
+// https://developers.line.biz/ja/reference/messaging-api/#message-objects
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MessageContent {
+    #[serde(rename_all = "camelCase")]
+    Text {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        quote_token: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Sticker {
+        package_id: String,
+        sticker_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        quote_token: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Image {
+        original_content_url: String,
+        preview_image_url: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Video {
+        original_content_url: String,
+        preview_image_url: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tracking_id: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Audio {
+        original_content_url: String,
+        duration: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Location {
+        title: String,
+        address: String,
+        latitude: f64,
+        longitude: f64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Flex {
+        alt_text: String,
+        contents: serde_json::Value,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    #[serde(flatten)]
+    pub content: MessageContent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Sender>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quick_reply: Option<serde_json::Value>,
+}
+
+impl Message {
+    pub fn new(content: MessageContent) -> Self {
+        Self {
+            content,
+            sender: None,
+            quick_reply: None,
+        }
+    }
+
+    pub fn text(text: &str) -> Self {
+        Self::new(MessageContent::Text {
+            text: text.to_owned(),
+            quote_token: None,
+        })
+    }
+
+    pub fn sticker(package_id: &str, sticker_id: &str) -> Self {
+        Self::new(MessageContent::Sticker {
+            package_id: package_id.to_owned(),
+            sticker_id: sticker_id.to_owned(),
+            quote_token: None,
+        })
+    }
+
+    pub fn with_sender(mut self, sender: Sender) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// 引用元メッセージの `quoteToken` を設定する。
+    ///
+    /// 引用できるのはテキストとスタンプのみ。それ以外の種別では何もしない。
+    pub fn with_quote_token(mut self, token: &str) -> Self {
+        match &mut self.content {
+            MessageContent::Text { quote_token, .. }
+            | MessageContent::Sticker { quote_token, .. } => {
+                *quote_token = Some(token.to_owned());
+            }
+            _ => {}
+        }
+        self
+    }
+
+    pub fn quote_token(&self) -> Option<&str> {
+        match &self.content {
+            MessageContent::Text { quote_token, .. }
+            | MessageContent::Sticker { quote_token, .. } => quote_token.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn into_value(self) -> serde_json::Value {
+        // 文字列キーの構造体なのでシリアライズは失敗しない
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+impl From<Message> for serde_json::Value {
+    fn from(message: Message) -> Self {
+        message.into_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_message_text_with_sender_and_quote_token() {
+        let message = Message::text("Hello")
+            .with_sender(Sender::new(
+                Some("Bot"),
+                Some("https://example.com/icon.png"),
+            ))
+            .with_quote_token("q1");
+        assert_eq!(message.quote_token(), Some("q1"));
+        assert_eq!(
+            message.into_value(),
+            json!({
+                "type": "text",
+                "text": "Hello",
+                "quoteToken": "q1",
+                "sender": {"name": "Bot", "iconUrl": "https://example.com/icon.png"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_message_quote_token_ignored_for_image() {
+        let message = Message::new(MessageContent::Image {
+            original_content_url: "https://example.com/a.png".to_owned(),
+            preview_image_url: "https://example.com/b.png".to_owned(),
+        })
+        .with_quote_token("q1");
+        assert_eq!(message.quote_token(), None);
+        assert_eq!(
+            message.into_value(),
+            json!({
+                "type": "image",
+                "originalContentUrl": "https://example.com/a.png",
+                "previewImageUrl": "https://example.com/b.png"
+            })
+        );
+    }
+
+    #[test]
+    fn test_message_deserialize_sticker() {
+        let message: Message = serde_json::from_value(json!({
+            "type": "sticker",
+            "packageId": "446",
+            "stickerId": "1988",
+            "quoteToken": "q2"
+        }))
+        .unwrap();
+        assert_eq!(message.quote_token(), Some("q2"));
+        assert_eq!(message.sender, None);
+    }
+}
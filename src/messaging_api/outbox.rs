@@ -0,0 +1,627 @@
+//! クラッシュしても失われない送信キュー(アウトボックス)。
+//!
+//! push / multicast を [`Outbox::enqueue`] でいったん [`MessageStore`] に保存し、
+//! [`Outbox::drain`](または [`Outbox::run`])で送信する。少なくとも 1 回の配信
+//! (at-least-once)を保証するため、エントリーは送信に成功するまでストアに残る。
+//!
+//! 各エントリーは保存時に生成したリトライキーを持ち、再送(プロセス再起動後を含む)でも
+//! 同じキーを使う。そのため、送信済みなのに完了を記録する前に落ちた場合でも、再送は
+//! LINE 側で 409(受理済み)となり二重送信にはならず、完了として扱う。
+//!
+//! ただし LINE がリトライキーを覚えているのは最初の送信から 24 時間([`RETRY_KEY_TTL`])だけで、
+//! それを過ぎた再送は新しいリクエストとして受理され得る(二重送信になる)。そのため最初に
+//! 送信した時刻を [`OutboxEntry::first_sent_at`] に記録し、24 時間を過ぎた送信待ちは送らずに
+//! デッドレターへ移す。
+//!
+//! 一時的でないエラー(4xx など)や、`max_attempts` 回失敗したエントリーは
+//! デッドレターへ移り、[`Outbox::dead_letters`] で確認、[`Outbox::requeue_dead_letter`] で
+//! 再投入できる。リトライキーの期限が切れたものは、二重送信を承知のうえで
+//! [`Outbox::requeue_dead_letter_with_new_key`] を使ったときだけ再投入する。
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    LineOptions,
+    error::Error,
+    messaging_api::{post_v2_bot_message_multicast, post_v2_bot_message_push},
+    new_retry_key,
+};
+
+/// LINE がリトライキーを有効として扱う期間(最初の送信から 24 時間)。
+pub const RETRY_KEY_TTL: chrono::TimeDelta = chrono::TimeDelta::hours(24);
+
+/// アウトボックスに積む送信内容。
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", content = "body", rename_all = "camelCase")]
+pub enum OutboxMessage {
+    Push(post_v2_bot_message_push::RequestBody),
+    Multicast(post_v2_bot_message_multicast::RequestBody),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OutboxEntry {
+    pub id: String,
+    /// 送信のたびに使い回す `X-Line-Retry-Key`
+    pub retry_key: String,
+    pub message: OutboxMessage,
+    /// 失敗した送信の回数
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    /// このリトライキーで最初に送信した時刻。未送信なら `None`
+    #[serde(default)]
+    pub first_sent_at: Option<DateTime<Utc>>,
+    /// 直近の失敗内容(`Error::make_json` の値)
+    pub last_error: Option<serde_json::Value>,
+}
+
+impl OutboxEntry {
+    pub fn new(message: OutboxMessage) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            retry_key: new_retry_key(),
+            message,
+            attempts: 0,
+            created_at: Utc::now(),
+            first_sent_at: None,
+            last_error: None,
+        }
+    }
+
+    /// `now` の時点でリトライキーの期限([`RETRY_KEY_TTL`])が切れているか。未送信なら `false`。
+    pub fn is_retry_key_expired(&self, now: DateTime<Utc>) -> bool {
+        self.first_sent_at
+            .is_some_and(|first_sent_at| now - first_sent_at >= RETRY_KEY_TTL)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeadLetter {
+    pub entry: OutboxEntry,
+    /// 最後の失敗内容(`Error::make_json` の値)
+    pub error: serde_json::Value,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// アウトボックスの保存先。
+///
+/// 送信待ちとデッドレターはどちらも `id` で一意。送信待ちは `created_at` の古い順に返すこと。
+/// メソッドは同期的に呼ばれる(ファイル等の短い I/O を想定している)。
+pub trait MessageStore: Send + Sync {
+    /// 送信待ちのエントリーを追加または更新する。
+    fn save(&self, entry: &OutboxEntry) -> Result<(), Box<Error>>;
+    /// 送信待ちのエントリーを古い順に返す。
+    fn pending(&self) -> Result<Vec<OutboxEntry>, Box<Error>>;
+    /// 送信待ちから削除する(存在しなくてもエラーにしない)。
+    fn remove(&self, id: &str) -> Result<(), Box<Error>>;
+    /// 送信待ちからデッドレターへ移す。
+    fn move_to_dead_letter(&self, dead_letter: &DeadLetter) -> Result<(), Box<Error>>;
+    fn dead_letters(&self) -> Result<Vec<DeadLetter>, Box<Error>>;
+    /// デッドレターから取り出して削除する。
+    fn take_dead_letter(&self, id: &str) -> Result<Option<DeadLetter>, Box<Error>>;
+}
+
+/// メモリ上のストア(プロセスが落ちると失われる。テストや一時的な用途向け)。
+#[derive(Debug, Default)]
+pub struct MemoryMessageStore {
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    pending: BTreeMap<String, OutboxEntry>,
+    dead_letters: BTreeMap<String, DeadLetter>,
+}
+
+impl MemoryMessageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl MessageStore for MemoryMessageStore {
+    fn save(&self, entry: &OutboxEntry) -> Result<(), Box<Error>> {
+        self.lock().pending.insert(entry.id.clone(), entry.clone());
+        Ok(())
+    }
+
+    fn pending(&self) -> Result<Vec<OutboxEntry>, Box<Error>> {
+        let mut entries: Vec<_> = self.lock().pending.values().cloned().collect();
+        entries.sort_by_key(|it| it.created_at);
+        Ok(entries)
+    }
+
+    fn remove(&self, id: &str) -> Result<(), Box<Error>> {
+        self.lock().pending.remove(id);
+        Ok(())
+    }
+
+    fn move_to_dead_letter(&self, dead_letter: &DeadLetter) -> Result<(), Box<Error>> {
+        let mut state = self.lock();
+        state.pending.remove(&dead_letter.entry.id);
+        state
+            .dead_letters
+            .insert(dead_letter.entry.id.clone(), dead_letter.clone());
+        Ok(())
+    }
+
+    fn dead_letters(&self) -> Result<Vec<DeadLetter>, Box<Error>> {
+        let mut dead_letters: Vec<_> = self.lock().dead_letters.values().cloned().collect();
+        dead_letters.sort_by_key(|it| it.failed_at);
+        Ok(dead_letters)
+    }
+
+    fn take_dead_letter(&self, id: &str) -> Result<Option<DeadLetter>, Box<Error>> {
+        Ok(self.lock().dead_letters.remove(id))
+    }
+}
+
+/// ディレクトリに 1 エントリー 1 JSON ファイルで保存するストア。
+///
+/// `pending/` と `dead/` の 2 つのサブディレクトリを使う。書き込みは一時ファイルへ書いて
+/// ディスクへ同期してからリネームし、ディレクトリも同期するので、電源断などで途中で落ちても
+/// 壊れたエントリーは残らない。デッドレターへの移動は
+/// 「`dead/` へ書く → `pending/` から消す」の順なので、間で落ちると両方に残り得るが、
+/// 送信待ちは同じリトライキーで再送されるだけなので二重送信にはならない。
+#[derive(Debug)]
+pub struct FileMessageStore {
+    dir: PathBuf,
+}
+
+const PENDING_DIR: &str = "pending";
+const DEAD_DIR: &str = "dead";
+
+impl FileMessageStore {
+    /// `dir` 以下を保存先にする(無ければ作る)。
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self, Box<Error>> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(dir.join(PENDING_DIR)).map_err(Error::from)?;
+        std::fs::create_dir_all(dir.join(DEAD_DIR)).map_err(Error::from)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, sub: &str, id: &str) -> Result<PathBuf, Box<Error>> {
+        // id はファイル名になるので、パス区切り等を含むものは受け付けない
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Box::new(Error::Invalid(format!("outbox id: {id}"))));
+        }
+        Ok(self.dir.join(sub).join(format!("{id}.json")))
+    }
+
+    // 電源断などでも空や欠けたファイルが残らないよう、中身を同期してからリネームし、
+    // リネームもディレクトリを同期して確定させる
+    fn write<T: Serialize>(&self, path: &Path, value: &T) -> Result<(), Box<Error>> {
+        let tmp = path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec(value).map_err(Error::from)?;
+        let mut file = std::fs::File::create(&tmp).map_err(Error::from)?;
+        file.write_all(&bytes).map_err(Error::from)?;
+        file.sync_all().map_err(Error::from)?;
+        drop(file);
+        std::fs::rename(&tmp, path).map_err(Error::from)?;
+        if let Some(dir) = path.parent() {
+            Self::sync_dir(dir)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn sync_dir(dir: &Path) -> Result<(), Box<Error>> {
+        std::fs::File::open(dir)
+            .and_then(|it| it.sync_all())
+            .map_err(Error::from)?;
+        Ok(())
+    }
+
+    // Windows ではディレクトリを開いて同期できない
+    #[cfg(not(unix))]
+    fn sync_dir(_dir: &Path) -> Result<(), Box<Error>> {
+        Ok(())
+    }
+
+    fn read_all<T: DeserializeOwned>(&self, sub: &str) -> Result<Vec<T>, Box<Error>> {
+        let mut values = vec![];
+        for dir_entry in std::fs::read_dir(self.dir.join(sub)).map_err(Error::from)? {
+            let path = dir_entry.map_err(Error::from)?.path();
+            // 書きかけの一時ファイルは無視する
+            if path.extension().is_some_and(|it| it == "json") {
+                let bytes = std::fs::read(&path).map_err(Error::from)?;
+                values.push(serde_json::from_slice(&bytes).map_err(Error::from)?);
+            }
+        }
+        Ok(values)
+    }
+
+    fn remove_file(path: &Path) -> Result<(), Box<Error>> {
+        match std::fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(Box::new(err.into())),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MessageStore for FileMessageStore {
+    fn save(&self, entry: &OutboxEntry) -> Result<(), Box<Error>> {
+        self.write(&self.path(PENDING_DIR, &entry.id)?, entry)
+    }
+
+    fn pending(&self) -> Result<Vec<OutboxEntry>, Box<Error>> {
+        let mut entries: Vec<OutboxEntry> = self.read_all(PENDING_DIR)?;
+        entries.sort_by_key(|it| it.created_at);
+        Ok(entries)
+    }
+
+    fn remove(&self, id: &str) -> Result<(), Box<Error>> {
+        Self::remove_file(&self.path(PENDING_DIR, id)?)
+    }
+
+    fn move_to_dead_letter(&self, dead_letter: &DeadLetter) -> Result<(), Box<Error>> {
+        self.write(&self.path(DEAD_DIR, &dead_letter.entry.id)?, dead_letter)?;
+        self.remove(&dead_letter.entry.id)
+    }
+
+    fn dead_letters(&self) -> Result<Vec<DeadLetter>, Box<Error>> {
+        let mut dead_letters: Vec<DeadLetter> = self.read_all(DEAD_DIR)?;
+        dead_letters.sort_by_key(|it| it.failed_at);
+        Ok(dead_letters)
+    }
+
+    fn take_dead_letter(&self, id: &str) -> Result<Option<DeadLetter>, Box<Error>> {
+        let path = self.path(DEAD_DIR, id)?;
+        let dead_letter = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(Error::from)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(Box::new(err.into())),
+        };
+        Self::remove_file(&path)?;
+        Ok(Some(dead_letter))
+    }
+}
+
+/// [`Outbox::drain`] 1 回分の結果。
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DrainReport {
+    /// 送信できた(409 の受理済みを含む)件数
+    pub sent: usize,
+    /// 一時的なエラーで失敗し、送信待ちに残した件数
+    pub retrying: usize,
+    /// デッドレターへ移した件数
+    pub dead_lettered: usize,
+}
+
+/// 送信キューとそれを送信するワーカー。
+#[derive(Debug)]
+pub struct Outbox<S: MessageStore> {
+    store: S,
+    channel_access_token: String,
+    options: LineOptions,
+    max_attempts: u32,
+}
+
+impl<S: MessageStore> Outbox<S> {
+    /// リトライキーを必ず付けて送るため、`options` がリトライしない設定
+    /// (`try_count` が 1 以下でリトライ方針も未設定)なら `try_count` を 2 にする。
+    pub fn new(store: S, channel_access_token: &str, options: &LineOptions) -> Self {
+        let mut options = options.clone();
+        if !options.may_retry() {
+            options.try_count = Some(2);
+        }
+        Self {
+            store,
+            channel_access_token: channel_access_token.to_owned(),
+            options,
+            max_attempts: 5,
+        }
+    }
+
+    /// 1 エントリーあたりの送信の最大回数(デフォルト 5)。超えるとデッドレターへ移す。
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// 送信内容をリトライキー付きで保存する。送信は [`drain`](Self::drain) で行う。
+    pub fn enqueue(&self, message: OutboxMessage) -> Result<OutboxEntry, Box<Error>> {
+        let entry = OutboxEntry::new(message);
+        self.store.save(&entry)?;
+        Ok(entry)
+    }
+
+    pub fn enqueue_push(
+        &self,
+        body: post_v2_bot_message_push::RequestBody,
+    ) -> Result<OutboxEntry, Box<Error>> {
+        self.enqueue(OutboxMessage::Push(body))
+    }
+
+    pub fn enqueue_multicast(
+        &self,
+        body: post_v2_bot_message_multicast::RequestBody,
+    ) -> Result<OutboxEntry, Box<Error>> {
+        self.enqueue(OutboxMessage::Multicast(body))
+    }
+
+    async fn send(&self, entry: &OutboxEntry) -> Result<(), Box<Error>> {
+        let retry_key = Some(entry.retry_key.clone());
+        // リトライキー付きなので 409(受理済み)も成功として返る
+        match &entry.message {
+            OutboxMessage::Push(body) => post_v2_bot_message_push::execute(
+                body.clone(),
+                &self.channel_access_token,
+                &self.options,
+                retry_key,
+            )
+            .await
+            .map(|_| ()),
+            OutboxMessage::Multicast(body) => post_v2_bot_message_multicast::execute(
+                body.clone(),
+                &self.channel_access_token,
+                &self.options,
+                retry_key,
+            )
+            .await
+            .map(|_| ()),
+        }
+    }
+
+    /// 送信待ちを古い順にすべて送る。
+    ///
+    /// 成功したエントリーは削除する。一時的なエラー([`Error::is_retryable`])は試行回数を
+    /// 記録して送信待ちに残し、それ以外のエラーや `max_attempts` 回目の失敗はデッドレターへ
+    /// 移す。リトライキーの期限が切れたエントリーは送らずにデッドレターへ移す。
+    /// ストアの読み書きに失敗した場合はそこで中断してエラーを返す。
+    pub async fn drain(&self) -> Result<DrainReport, Box<Error>> {
+        let mut report = DrainReport::default();
+        for mut entry in self.store.pending()? {
+            let now = Utc::now();
+            if entry.is_retry_key_expired(now) {
+                self.store.move_to_dead_letter(&DeadLetter {
+                    error: retry_key_expired_error(&entry).make_json(),
+                    entry,
+                    failed_at: now,
+                })?;
+                report.dead_lettered += 1;
+                continue;
+            }
+            if entry.first_sent_at.is_none() {
+                // 送信後、結果を記録する前に落ちても期限が分かるよう、送る前に保存する
+                entry.first_sent_at = Some(now);
+                self.store.save(&entry)?;
+            }
+            match self.send(&entry).await {
+                Ok(()) => {
+                    self.store.remove(&entry.id)?;
+                    report.sent += 1;
+                }
+                Err(err) => {
+                    tracing::debug!("outbox {} failed: {err:?}", entry.id);
+                    entry.attempts += 1;
+                    let error = err.make_json();
+                    if err.is_retryable() && entry.attempts < self.max_attempts {
+                        entry.last_error = Some(error);
+                        self.store.save(&entry)?;
+                        report.retrying += 1;
+                    } else {
+                        self.store.move_to_dead_letter(&DeadLetter {
+                            entry,
+                            error,
+                            failed_at: Utc::now(),
+                        })?;
+                        report.dead_lettered += 1;
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// [`drain`](Self::drain) を `interval` ごとに繰り返す。ストアのエラーでのみ終了する。
+    pub async fn run(&self, interval: Duration) -> Result<(), Box<Error>> {
+        loop {
+            let report = self.drain().await?;
+            if report != DrainReport::default() {
+                tracing::debug!("outbox drained: {report:?}");
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    pub fn dead_letters(&self) -> Result<Vec<DeadLetter>, Box<Error>> {
+        self.store.dead_letters()
+    }
+
+    /// デッドレターを送信待ちへ戻す(試行回数はリセットし、リトライキーはそのまま使う)。
+    ///
+    /// リトライキーの期限([`RETRY_KEY_TTL`])が切れている場合は、再送が二重送信になり得るので
+    /// 戻さずに `Error::Invalid` を返す(デッドレターはそのまま残る)。
+    pub fn requeue_dead_letter(&self, id: &str) -> Result<Option<OutboxEntry>, Box<Error>> {
+        let Some(dead_letter) = self.store.take_dead_letter(id)? else {
+            return Ok(None);
+        };
+        if dead_letter.entry.is_retry_key_expired(Utc::now()) {
+            let err = retry_key_expired_error(&dead_letter.entry);
+            self.store.move_to_dead_letter(&dead_letter)?;
+            return Err(err);
+        }
+        self.requeue(dead_letter).map(Some)
+    }
+
+    /// デッドレターを新しいリトライキーで送信待ちへ戻す。
+    ///
+    /// 元のキーで送った分が LINE に受理済みだった場合は二重送信になる。リトライキーの期限が
+    /// 切れたデッドレターを、それを承知で送り直すときに使う。
+    pub fn requeue_dead_letter_with_new_key(
+        &self,
+        id: &str,
+    ) -> Result<Option<OutboxEntry>, Box<Error>> {
+        let Some(mut dead_letter) = self.store.take_dead_letter(id)? else {
+            return Ok(None);
+        };
+        dead_letter.entry.retry_key = new_retry_key();
+        dead_letter.entry.first_sent_at = None;
+        self.requeue(dead_letter).map(Some)
+    }
+
+    fn requeue(&self, dead_letter: DeadLetter) -> Result<OutboxEntry, Box<Error>> {
+        let mut entry = dead_letter.entry;
+        entry.attempts = 0;
+        entry.last_error = Some(dead_letter.error);
+        self.store.save(&entry)?;
+        Ok(entry)
+    }
+}
+
+fn retry_key_expired_error(entry: &OutboxEntry) -> Box<Error> {
+    Box::new(Error::Invalid(format!(
+        "outbox {}: retry key has expired (first sent at {})",
+        entry.id,
+        entry
+            .first_sent_at
+            .map(|it| it.to_rfc3339())
+            .unwrap_or_default()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn make_entry() -> OutboxEntry {
+        OutboxEntry::new(OutboxMessage::Push(
+            post_v2_bot_message_push::RequestBody::new(
+                "U123456789",
+                vec![json!({"type": "text", "text": "Hello!"})],
+            )
+            .unwrap(),
+        ))
+    }
+
+    fn check_store(store: &impl MessageStore) {
+        let first = make_entry();
+        let mut second = make_entry();
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+        store.save(&second).unwrap();
+        store.save(&first).unwrap();
+        let pending = store.pending().unwrap();
+        assert_eq!(
+            pending.iter().map(|it| it.id.as_str()).collect::<Vec<_>>(),
+            vec![first.id.as_str(), second.id.as_str()]
+        );
+        assert_eq!(pending[0].retry_key, first.retry_key);
+
+        store
+            .move_to_dead_letter(&DeadLetter {
+                entry: second.clone(),
+                error: json!({"message": "bad"}),
+                failed_at: Utc::now(),
+            })
+            .unwrap();
+        store.remove(&first.id).unwrap();
+        // 存在しない id の削除はエラーにしない
+        store.remove(&first.id).unwrap();
+        assert!(store.pending().unwrap().is_empty());
+
+        let dead_letters = store.dead_letters().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].entry.id, second.id);
+        assert_eq!(dead_letters[0].error["message"], "bad");
+        assert!(store.take_dead_letter(&second.id).unwrap().is_some());
+        assert!(store.take_dead_letter(&second.id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_memory_message_store() {
+        check_store(&MemoryMessageStore::new());
+    }
+
+    // 別インスタンス(再起動後を想定)からも同じ内容が読める
+    #[test]
+    fn test_file_message_store() {
+        let dir = std::env::temp_dir().join(format!("lineapi-outbox-{}", uuid::Uuid::new_v4()));
+        check_store(&FileMessageStore::new(&dir).unwrap());
+
+        let entry = make_entry();
+        FileMessageStore::new(&dir).unwrap().save(&entry).unwrap();
+        let pending = FileMessageStore::new(&dir).unwrap().pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].retry_key, entry.retry_key);
+        assert!(
+            FileMessageStore::new(&dir)
+                .unwrap()
+                .remove("../escape")
+                .is_err()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // 以前の形式(first_sent_at なし)のエントリーも読める
+    #[test]
+    fn test_outbox_entry_without_first_sent_at() {
+        let mut value = serde_json::to_value(make_entry()).unwrap();
+        value.as_object_mut().unwrap().remove("first_sent_at");
+        let entry: OutboxEntry = serde_json::from_value(value).unwrap();
+        assert_eq!(entry.first_sent_at, None);
+        assert!(!entry.is_retry_key_expired(Utc::now()));
+    }
+
+    // リトライキーの期限が切れたものは送らずにデッドレターへ移し、明示しない限り戻さない
+    #[tokio::test]
+    async fn test_outbox_retry_key_expired() {
+        let outbox = Outbox::new(
+            MemoryMessageStore::new(),
+            "test_channel_access_token",
+            // 送信しないことの確認なので、届かない URL にしておく
+            &LineOptions::builder()
+                .with_prefix_url("http://127.0.0.1:9")
+                .build(),
+        );
+        let mut entry = make_entry();
+        entry.first_sent_at = Some(Utc::now() - RETRY_KEY_TTL - chrono::Duration::seconds(1));
+        outbox.store().save(&entry).unwrap();
+
+        let report = outbox.drain().await.unwrap();
+        assert_eq!(
+            report,
+            DrainReport {
+                dead_lettered: 1,
+                ..Default::default()
+            }
+        );
+        let dead_letters = outbox.dead_letters().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].entry.attempts, 0);
+
+        assert!(outbox.requeue_dead_letter(&entry.id).is_err());
+        assert_eq!(outbox.dead_letters().unwrap().len(), 1);
+        assert!(outbox.store().pending().unwrap().is_empty());
+
+        let requeued = outbox
+            .requeue_dead_letter_with_new_key(&entry.id)
+            .unwrap()
+            .unwrap();
+        assert_ne!(requeued.retry_key, entry.retry_key);
+        assert_eq!(requeued.first_sent_at, None);
+        assert!(outbox.dead_letters().unwrap().is_empty());
+        assert_eq!(outbox.store().pending().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,79 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::Error,
+    execute_api, make_url,
+    messaging_api::audience_group::{CreateResponseBody, validate_description},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#create-imp-audience-group
+const URL: &str = "/v2/bot/audienceGroup/imp";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_audience_group_imp", "POST", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub description: String,
+    pub request_id: String,
+}
+
+impl RequestBody {
+    pub fn new(description: &str, request_id: &str) -> Result<Self, Box<Error>> {
+        validate_description(description)?;
+        Ok(Self {
+            description: description.to_string(),
+            request_id: request_id.to_string(),
+        })
+    }
+}
+
+pub type ResponseBody = CreateResponseBody;
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // REQUEST_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_audience_group_imp -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_audience_group_imp() {
+        let request_id = std::env::var("REQUEST_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new("test imp audience", &request_id).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
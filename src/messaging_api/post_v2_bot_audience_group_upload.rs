@@ -0,0 +1,89 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_length},
+    execute_api, make_url,
+    messaging_api::audience_group::{Audience, CreateResponseBody, validate_description},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#create-upload-audience-group
+const URL: &str = "/v2/bot/audienceGroup/upload";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_audience_group_upload", "POST", URL);
+
+/// JSON で 1 リクエストに含められるユーザー ID の上限。
+pub const MAX_AUDIENCES: usize = 10000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_ifa_audience: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_description: Option<String>,
+    pub audiences: Vec<Audience>,
+}
+
+impl RequestBody {
+    pub fn new(description: &str, audiences: Vec<Audience>) -> Result<Self, Box<Error>> {
+        validate_description(description)?;
+        check_length("audiences", audiences.len(), 0, MAX_AUDIENCES)?;
+        Ok(Self {
+            description: description.to_string(),
+            is_ifa_audience: None,
+            upload_description: None,
+            audiences,
+        })
+    }
+}
+
+pub type ResponseBody = CreateResponseBody;
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{messaging_api::audience_group::Audience, option::LineOptions};
+
+    // USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_audience_group_upload -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_audience_group_upload() {
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new("test audience", vec![Audience::new(&user_id)]).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
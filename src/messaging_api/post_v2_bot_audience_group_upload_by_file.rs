@@ -0,0 +1,123 @@
+use reqwest::{
+    RequestBuilder,
+    multipart::{Form, Part},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_length},
+    execute_api, make_data_url,
+    messaging_api::audience_group::{CreateResponseBody, validate_description},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#create-upload-audience-group-by-file
+// ファイルアップロードはデータ用ホスト(api-data.line.me)に送る。
+const URL: &str = "/v2/bot/audienceGroup/upload/byFile";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_audience_group_upload_by_file", "POST", URL);
+
+/// 1 ファイルに含められるユーザー ID の上限。
+pub const MAX_AUDIENCES: usize = 1_500_000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_ifa_audience: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_description: Option<String>,
+    // ファイルの中身(改行区切り)。ログには件数だけを出す
+    #[serde(skip)]
+    pub audiences: Vec<String>,
+}
+
+impl RequestBody {
+    pub fn new(description: &str, audiences: Vec<String>) -> Result<Self, Box<Error>> {
+        validate_description(description)?;
+        check_length("audiences", audiences.len(), 1, MAX_AUDIENCES)?;
+        Ok(Self {
+            description: description.to_string(),
+            is_ifa_audience: None,
+            upload_description: None,
+            audiences,
+        })
+    }
+
+    fn make_form(&self) -> Form {
+        let mut form = Form::new().text("description", self.description.clone());
+        if let Some(is_ifa_audience) = self.is_ifa_audience {
+            form = form.text("isIfaAudience", is_ifa_audience.to_string());
+        }
+        if let Some(upload_description) = &self.upload_description {
+            form = form.text("uploadDescription", upload_description.clone());
+        }
+        form.part(
+            "file",
+            make_file_part(self.audiences.join("\n").into_bytes()),
+        )
+    }
+}
+
+// text/plain は常に正しい MIME なので失敗しない
+pub(crate) fn make_file_part(bytes: Vec<u8>) -> Part {
+    Part::bytes(bytes)
+        .file_name("audiences.txt")
+        .mime_str("text/plain")
+        .unwrap()
+}
+
+pub type ResponseBody = CreateResponseBody;
+
+/// multipart のボディは `try_clone` できないため、コールバックの [`LineRequestLog`](crate::LineRequestLog)
+/// では `headers` / `method` / `path` が `None` になる。
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_data_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).multipart(body.make_form());
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || {
+            let mut value = crate::serialize_log_body(&body);
+            value["audiencesCount"] = serde_json::json!(body.audiences.len());
+            value
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_audience_group_upload_by_file -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_audience_group_upload_by_file() {
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new("test audience by file", vec![user_id]).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
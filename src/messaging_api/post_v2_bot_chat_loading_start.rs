@@ -0,0 +1,92 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_not_empty, check_range},
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#display-a-loading-indicator
+const URL: &str = "/v2/bot/chat/loading/start";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_chat_loading_start", "POST", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub chat_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loading_seconds: Option<u8>,
+}
+
+impl RequestBody {
+    // loading_seconds は 5〜60 の 5 の倍数(省略時は LINE 側で 20 秒)
+    pub fn new(chat_id: &str, loading_seconds: Option<u8>) -> Result<Self, Box<Error>> {
+        check_not_empty("chat_id", chat_id)?;
+        if let Some(loading_seconds) = loading_seconds {
+            check_range("loading_seconds", loading_seconds as i64, 5, 60, Some(5))?;
+        }
+        Ok(Self {
+            chat_id: chat_id.to_string(),
+            loading_seconds,
+        })
+    }
+}
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    #[test]
+    fn test_request_body_loading_seconds() {
+        assert!(super::RequestBody::new("U123", None).is_ok());
+        assert!(super::RequestBody::new("U123", Some(5)).is_ok());
+        assert!(super::RequestBody::new("U123", Some(60)).is_ok());
+        assert!(super::RequestBody::new("U123", Some(0)).is_err());
+        assert!(super::RequestBody::new("U123", Some(7)).is_err());
+        assert!(super::RequestBody::new("U123", Some(65)).is_err());
+        assert!(super::RequestBody::new("", None).is_err());
+    }
+
+    // USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_chat_loading_start -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_chat_loading_start() {
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new(&user_id, Some(10)).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -0,0 +1,77 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_not_empty},
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#mark-as-read
+// Webhook の `markAsReadToken` を使って既読にする。
+// 自動既読([`MarkAsReadMode::Auto`](crate::messaging_api::get_v2_bot_info::MarkAsReadMode))の
+// チャネルでは効果がないため、`get_v2_bot_info` で `manual` であることを確認して使う。
+const URL: &str = "/v2/bot/chat/markAsRead";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_chat_mark_as_read", "POST", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub mark_as_read_token: String,
+}
+
+impl RequestBody {
+    pub fn new(mark_as_read_token: &str) -> Result<Self, Box<Error>> {
+        check_not_empty("mark_as_read_token", mark_as_read_token)?;
+        Ok(Self {
+            mark_as_read_token: mark_as_read_token.to_string(),
+        })
+    }
+}
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // MARK_AS_READ_TOKEN=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_chat_mark_as_read -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_chat_mark_as_read() {
+        let mark_as_read_token = std::env::var("MARK_AS_READ_TOKEN").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new(&mark_as_read_token).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -0,0 +1,56 @@
+use reqwest::RequestBuilder;
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#leave-group
+const URL: &str = "/v2/bot/group";
+const ENDPOINT: Endpoint = Endpoint::new(
+    "post_v2_bot_group_leave",
+    "POST",
+    "/v2/bot/group/{groupId}/leave",
+);
+
+pub fn build(group_id: &str, channel_access_token: &str, options: &LineOptions) -> RequestBuilder {
+    let url = make_url(&format!("{URL}/{group_id}/leave"), options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    group_id: &str,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(group_id, channel_access_token, options),
+        options,
+        None,
+        || serde_json::Value::Null,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // GROUP_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_group_leave -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_group_leave() {
+        let group_id = std::env::var("GROUP_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let (response, header) = super::execute(&group_id, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -0,0 +1,83 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_not_empty},
+    execute_api, make_url,
+};
+
+// https://developers.line.biz/ja/reference/partner-docs/#mark-messages-from-users-as-read
+// チャット ID(ユーザー ID)指定の旧形式。利用できるのは申請済みの法人ユーザーのみで、
+// 通常は `post_v2_bot_chat_mark_as_read`(markAsReadToken 形式)を使う。
+const URL: &str = "/v2/bot/message/markAsRead";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_message_mark_as_read", "POST", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Chat {
+    pub user_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RequestBody {
+    pub chat: Chat,
+}
+
+impl RequestBody {
+    pub fn new(user_id: &str) -> Result<Self, Box<Error>> {
+        check_not_empty("user_id", user_id)?;
+        Ok(Self {
+            chat: Chat {
+                user_id: user_id.to_string(),
+            },
+        })
+    }
+}
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_message_mark_as_read -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_message_mark_as_read() {
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new(&user_id).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -0,0 +1,107 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_length},
+    execute_api, make_url, resolve_retry_key,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#send-multicast-message
+const URL: &str = "/v2/bot/message/multicast";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_message_multicast", "POST", URL);
+
+/// 1 回のリクエストで送れる宛先の最大数
+pub const MAX_TO: usize = 500;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub to: Vec<String>,
+    pub messages: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification_disabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_aggregation_units: Option<Vec<String>>,
+}
+
+impl RequestBody {
+    pub fn new(to: Vec<String>, messages: Vec<serde_json::Value>) -> Result<Self, Box<Error>> {
+        check_length("to", to.len(), 1, MAX_TO)?;
+        check_length("messages", messages.len(), 1, 5)?;
+        Ok(Self {
+            to,
+            messages,
+            notification_disabled: None,
+            custom_aggregation_units: None,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>, // リトライの時に入る可能性がある
+    #[serde(flatten)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.post(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+    retry_key: Option<String>,
+) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        resolve_retry_key(retry_key, options),
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_post_v2_bot_message_multicast -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_post_v2_bot_message_multicast() {
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::builder().with_try_count(3).build();
+        let body = super::RequestBody::new(
+            vec![user_id],
+            vec![serde_json::json!({
+                "type": "text",
+                "text": "Hello, world!"
+            })],
+        )
+        .unwrap();
+        let (response, header) = super::execute(
+            body,
+            &channel_access_token,
+            &options,
+            Some(crate::new_retry_key()),
+        )
+        .await
+        .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
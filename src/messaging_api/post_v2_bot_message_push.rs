@@ -1,13 +1,15 @@
-use reqwest::RequestBuilder;
+use reqwest::{RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_length, check_not_empty},
+    execute_api, execute_api_with_status, make_url, resolve_retry_key,
 };
 
 // https://developers.line.biz/ja/reference/messaging-api/#send-push-message
 const URL: &str = "/v2/bot/message/push";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_message_push", "POST", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -22,18 +24,8 @@ pub struct RequestBody {
 
 impl RequestBody {
     pub fn new(to: &str, messages: Vec<serde_json::Value>) -> Result<Self, Box<Error>> {
-        if to.is_empty() {
-            return Err(Box::new(Error::Invalid("to is empty".to_string())));
-        }
-        if messages.is_empty() {
-            return Err(Box::new(Error::Invalid("messages is empty".to_string())));
-        }
-        if messages.len() > 5 {
-            return Err(Box::new(Error::Invalid(format!(
-                "messages is too long: {}",
-                messages.len()
-            ))));
-        }
+        check_not_empty("to", to)?;
+        check_length("messages", messages.len(), 1, 5)?;
         Ok(Self {
             to: to.to_string(),
             messages,
@@ -43,26 +35,68 @@ impl RequestBody {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct SentMessage {
-    id: String,
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SentMessage {
+    pub id: String,
     #[serde(alias = "quoteToken")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    quote_token: Option<String>,
+    pub quote_token: Option<String>,
     #[serde(flatten)]
     pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResponseBody {
-    #[serde(alias = "sentMessages")]
-    sent_messages: Vec<SentMessage>,
+    // 409(リトライキー受理済み)の応答では省略され得るので default とする
+    #[serde(alias = "sentMessages", default)]
+    pub sent_messages: Vec<SentMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<String>, // リトライの時に入る可能性がある
+    pub message: Option<String>, // リトライの時に入る可能性がある
     #[serde(flatten)]
     pub extra: std::collections::HashMap<String, serde_json::Value>,
 }
 
+impl ResponseBody {
+    pub fn sent_messages(&self) -> &[SentMessage] {
+        &self.sent_messages
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn message_ids(&self) -> Vec<&str> {
+        self.sent_messages.iter().map(|it| it.id.as_str()).collect()
+    }
+}
+
+/// push の結果が新規送信か、同じリトライキーで既に受理済みだったかを表す。
+///
+/// `allow_conflict`(リトライキー指定時)の 409 は `execute` では成功として扱われるため、
+/// 両者を区別したい場合は [`execute_with_outcome`] を使う。
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum PushOutcome {
+    Sent,
+    AlreadyAccepted { accepted_request_id: Option<String> },
+}
+
+impl PushOutcome {
+    // https://developers.line.biz/ja/docs/messaging-api/retrying-api-request/#flow-of-api-request-retry
+    pub fn from_response(status_code: StatusCode, line_header: &LineResponseHeader) -> Self {
+        if status_code == StatusCode::CONFLICT {
+            Self::AlreadyAccepted {
+                accepted_request_id: line_header.accepted_request_id.clone(),
+            }
+        } else {
+            Self::Sent
+        }
+    }
+
+    pub fn is_already_accepted(&self) -> bool {
+        matches!(self, Self::AlreadyAccepted { .. })
+    }
+}
+
 pub fn build(
     body: &RequestBody,
     channel_access_token: &str,
@@ -83,15 +117,33 @@ pub async fn execute(
     retry_key: Option<String>,
 ) -> Result<(ResponseBody, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(&body, channel_access_token, options),
         options,
-        is_standard_retry,
-        retry_key,
+        resolve_retry_key(retry_key, options),
         || crate::serialize_log_body(&body),
     )
     .await
 }
 
+pub async fn execute_with_outcome(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+    retry_key: Option<String>,
+) -> Result<(ResponseBody, PushOutcome, LineResponseHeader), Box<Error>> {
+    let (response, line_header, status_code) = execute_api_with_status(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        resolve_retry_key(retry_key, options),
+        || crate::serialize_log_body(&body),
+    )
+    .await?;
+    let outcome = PushOutcome::from_response(status_code, &line_header);
+    Ok((response, outcome, line_header))
+}
+
 #[cfg(test)]
 mod tests {
     use uuid::Uuid;
@@ -2,12 +2,13 @@ use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error, execute_api,
-    is_standard_retry, make_url,
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url,
 };
 
 // https://developers.line.biz/ja/reference/messaging-api/#validate-message-objects-of-push-message
 const URL: &str = "/v2/bot/message/validate/push";
+const ENDPOINT: Endpoint = Endpoint::new("post_v2_bot_message_validate_push", "POST", URL);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestBody {
@@ -33,9 +34,9 @@ pub async fn execute(
     options: &LineOptions,
 ) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
     execute_api(
+        ENDPOINT,
         || build(&body, channel_access_token, options),
         options,
-        is_standard_retry,
         None,
         || crate::serialize_log_body(&body),
     )
@@ -0,0 +1,82 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout, error::Error,
+    execute_api, make_url, messaging_api::audience_group::validate_description,
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#set-description-audience-group
+const URL: &str = "/v2/bot/audienceGroup";
+const ENDPOINT: Endpoint = Endpoint::new(
+    "put_v2_bot_audience_group_update_description",
+    "PUT",
+    "/v2/bot/audienceGroup/{audienceGroupId}/updateDescription",
+);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RequestBody {
+    pub description: String,
+}
+
+impl RequestBody {
+    pub fn new(description: &str) -> Result<Self, Box<Error>> {
+        validate_description(description)?;
+        Ok(Self {
+            description: description.to_string(),
+        })
+    }
+}
+
+pub fn build(
+    audience_group_id: i64,
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(
+        &format!("{URL}/{audience_group_id}/updateDescription"),
+        options,
+    );
+    let client = reqwest::Client::new();
+    let mut request_builder = client.put(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    audience_group_id: i64,
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(audience_group_id, &body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // AUDIENCE_GROUP_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_put_v2_bot_audience_group_update_description -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_put_v2_bot_audience_group_update_description() {
+        let audience_group_id: i64 = std::env::var("AUDIENCE_GROUP_ID").unwrap().parse().unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new("renamed audience").unwrap();
+        let (response, header) =
+            super::execute(audience_group_id, body, &channel_access_token, &options)
+                .await
+                .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
@@ -0,0 +1,82 @@
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_length},
+    execute_api, make_url,
+    messaging_api::{audience_group::Audience, post_v2_bot_audience_group_upload::MAX_AUDIENCES},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#update-upload-audience-group
+const URL: &str = "/v2/bot/audienceGroup/upload";
+const ENDPOINT: Endpoint = Endpoint::new("put_v2_bot_audience_group_upload", "PUT", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub audience_group_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_description: Option<String>,
+    pub audiences: Vec<Audience>,
+}
+
+impl RequestBody {
+    pub fn new(audience_group_id: i64, audiences: Vec<Audience>) -> Result<Self, Box<Error>> {
+        check_length("audiences", audiences.len(), 1, MAX_AUDIENCES)?;
+        Ok(Self {
+            audience_group_id,
+            upload_description: None,
+            audiences,
+        })
+    }
+}
+
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.put(&url).json(&body);
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || crate::serialize_log_body(&body),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{messaging_api::audience_group::Audience, option::LineOptions};
+
+    // AUDIENCE_GROUP_ID=xxx USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_put_v2_bot_audience_group_upload -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_put_v2_bot_audience_group_upload() {
+        let audience_group_id: i64 = std::env::var("AUDIENCE_GROUP_ID").unwrap().parse().unwrap();
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body =
+            super::RequestBody::new(audience_group_id, vec![Audience::new(&user_id)]).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
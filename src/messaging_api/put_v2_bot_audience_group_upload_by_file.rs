@@ -0,0 +1,101 @@
+use reqwest::{RequestBuilder, multipart::Form};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Endpoint, LineOptions, LineResponseHeader, apply_auth, apply_timeout,
+    error::{Error, check_length},
+    execute_api, make_data_url,
+    messaging_api::post_v2_bot_audience_group_upload_by_file::{MAX_AUDIENCES, make_file_part},
+};
+
+// https://developers.line.biz/ja/reference/messaging-api/#update-upload-audience-group-by-file
+// ファイルアップロードはデータ用ホスト(api-data.line.me)に送る。
+const URL: &str = "/v2/bot/audienceGroup/upload/byFile";
+const ENDPOINT: Endpoint = Endpoint::new("put_v2_bot_audience_group_upload_by_file", "PUT", URL);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestBody {
+    pub audience_group_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_description: Option<String>,
+    // ファイルの中身(改行区切り)。ログには件数だけを出す
+    #[serde(skip)]
+    pub audiences: Vec<String>,
+}
+
+impl RequestBody {
+    pub fn new(audience_group_id: i64, audiences: Vec<String>) -> Result<Self, Box<Error>> {
+        check_length("audiences", audiences.len(), 1, MAX_AUDIENCES)?;
+        Ok(Self {
+            audience_group_id,
+            upload_description: None,
+            audiences,
+        })
+    }
+
+    fn make_form(&self) -> Form {
+        let mut form = Form::new().text("audienceGroupId", self.audience_group_id.to_string());
+        if let Some(upload_description) = &self.upload_description {
+            form = form.text("uploadDescription", upload_description.clone());
+        }
+        form.part(
+            "file",
+            make_file_part(self.audiences.join("\n").into_bytes()),
+        )
+    }
+}
+
+/// multipart のボディは `try_clone` できないため、コールバックの [`LineRequestLog`](crate::LineRequestLog)
+/// では `headers` / `method` / `path` が `None` になる。
+pub fn build(
+    body: &RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> RequestBuilder {
+    let url = make_data_url(URL, options);
+    let client = reqwest::Client::new();
+    let mut request_builder = client.put(&url).multipart(body.make_form());
+    request_builder = apply_auth(request_builder, channel_access_token);
+    request_builder = apply_timeout(request_builder, options);
+    request_builder
+}
+
+pub async fn execute(
+    body: RequestBody,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<(serde_json::Value, LineResponseHeader), Box<Error>> {
+    execute_api(
+        ENDPOINT,
+        || build(&body, channel_access_token, options),
+        options,
+        None,
+        || {
+            let mut value = crate::serialize_log_body(&body);
+            value["audiencesCount"] = serde_json::json!(body.audiences.len());
+            value
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::option::LineOptions;
+
+    // AUDIENCE_GROUP_ID=xxx USER_ID=xxx CHANNEL_ACCESS_CODE=xxx cargo test test_messaging_api_put_v2_bot_audience_group_upload_by_file -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_messaging_api_put_v2_bot_audience_group_upload_by_file() {
+        let audience_group_id: i64 = std::env::var("AUDIENCE_GROUP_ID").unwrap().parse().unwrap();
+        let user_id = std::env::var("USER_ID").unwrap();
+        let channel_access_token = std::env::var("CHANNEL_ACCESS_CODE").unwrap();
+        let options = LineOptions::default();
+        let body = super::RequestBody::new(audience_group_id, vec![user_id]).unwrap();
+        let (response, header) = super::execute(body, &channel_access_token, &options)
+            .await
+            .unwrap();
+        println!("{response:?}");
+        println!("{header:?}");
+    }
+}
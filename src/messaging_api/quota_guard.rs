@@ -0,0 +1,219 @@
+//! 送信前に月間のメッセージ上限を確認するガード。
+//!
+//! [`get_v2_bot_message_quota`](上限)と [`get_v2_bot_message_quota_consumption`](使用数)を
+//! TTL の間キャッシュし、送信見込み数が残りを超える場合は API を呼ばずに
+//! [`Error::QuotaExceeded`] を返す。キャッシュしてから次に取り直すまでの間に
+//! [`QuotaGuard::acquire`] / [`QuotaGuard::record`] で数えた送信数も差し引く。
+//!
+//! [`QuotaGuard::acquire`] した分は [`QuotaGuard::confirm`](送信成功)か
+//! [`QuotaGuard::release`](送信失敗)を呼ぶまで送信中として扱い、取り直した使用数に
+//! まだ含まれていない可能性があるので、取り直した後も差し引き続ける。
+//! 取り直しは同時に 1 つだけ行う。
+//!
+//! 送信見込み数は multicast なら宛先数、broadcast なら配信対象の友だち数
+//! (メッセージオブジェクトの数によらず宛先 1 人につき 1 通)。
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::{
+    LineOptions,
+    error::{Error, QuotaExceeded},
+    messaging_api::{
+        get_v2_bot_insight_followers,
+        get_v2_bot_message_quota::{self, QuotaType},
+        get_v2_bot_message_quota_consumption,
+    },
+};
+
+#[derive(Debug, Clone)]
+struct QuotaState {
+    // None なら上限なし
+    limit: Option<i64>,
+    total_usage: i64,
+    // キャッシュ後に送信済みとして数えた送信数
+    local_sent: i64,
+    fetched_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct GuardState {
+    cached: Option<QuotaState>,
+    // acquire 後、confirm / release されていない送信数。取り直しても引き継ぐ
+    in_flight: i64,
+}
+
+impl GuardState {
+    fn remaining(&self) -> Option<i64> {
+        self.cached.as_ref().and_then(|state| {
+            state
+                .limit
+                .map(|limit| limit - state.total_usage - state.local_sent - self.in_flight)
+        })
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        match &self.cached {
+            Some(state) => state.fetched_at.elapsed() >= ttl,
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QuotaGuard {
+    channel_access_token: String,
+    options: LineOptions,
+    ttl: Duration,
+    state: Mutex<GuardState>,
+    // 取り直しを直列化する(同時に取り直すと送信数のリセットが重なる)
+    refreshing: AsyncMutex<()>,
+}
+
+impl QuotaGuard {
+    pub fn new(channel_access_token: &str, options: &LineOptions) -> Self {
+        Self {
+            channel_access_token: channel_access_token.to_owned(),
+            options: options.clone(),
+            ttl: Duration::from_secs(60),
+            state: Mutex::new(GuardState::default()),
+            refreshing: AsyncMutex::new(()),
+        }
+    }
+
+    /// 上限・使用数をキャッシュする時間(デフォルト 60 秒)。
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 残りのメッセージ数。上限がない(`type` が `none`)場合は `None`。
+    pub async fn remaining(&self) -> Result<Option<i64>, Box<Error>> {
+        self.refresh_if_expired().await?;
+        Ok(self.lock().remaining())
+    }
+
+    /// `cost` 通を送っても上限を超えないか確認する(送信数には数えない)。
+    pub async fn check(&self, cost: u64) -> Result<(), Box<Error>> {
+        self.refresh_if_expired().await?;
+        check_state(&self.lock(), cost)
+    }
+
+    /// 確認と同時に `cost` 通を送信中として数える。送信に成功したら [`QuotaGuard::confirm`]、
+    /// 失敗したら [`QuotaGuard::release`] を呼ぶ。どちらも呼ばなければ送信中のまま差し引き続ける。
+    pub async fn acquire(&self, cost: u64) -> Result<(), Box<Error>> {
+        self.refresh_if_expired().await?;
+        let mut state = self.lock();
+        check_state(&state, cost)?;
+        state.in_flight += cost as i64;
+        Ok(())
+    }
+
+    /// [`QuotaGuard::acquire`] した `cost` 通の送信に成功した。
+    pub fn confirm(&self, cost: u64) {
+        let mut state = self.lock();
+        state.in_flight = (state.in_flight - cost as i64).max(0);
+        if let Some(cached) = state.cached.as_mut() {
+            cached.local_sent += cost as i64;
+        }
+    }
+
+    /// [`QuotaGuard::acquire`] した `cost` 通の送信に失敗したので戻す。
+    pub fn release(&self, cost: u64) {
+        let mut state = self.lock();
+        state.in_flight = (state.in_flight - cost as i64).max(0);
+    }
+
+    /// ガードを通さずに送った分を数える。
+    pub fn record(&self, cost: u64) {
+        if let Some(cached) = self.lock().cached.as_mut() {
+            cached.local_sent += cost as i64;
+        }
+    }
+
+    /// 次の確認で上限・使用数を取り直す。送信中の数は引き継ぐ。
+    pub fn invalidate(&self) {
+        self.lock().cached = None;
+    }
+
+    async fn refresh_if_expired(&self) -> Result<(), Box<Error>> {
+        if !self.lock().is_expired(self.ttl) {
+            return Ok(());
+        }
+        let _refreshing = self.refreshing.lock().await;
+        // 待っている間に他のタスクが取り直していれば、それを使う
+        if !self.lock().is_expired(self.ttl) {
+            return Ok(());
+        }
+
+        let (quota, _) =
+            get_v2_bot_message_quota::execute(&self.channel_access_token, &self.options).await?;
+        let (consumption, _) = get_v2_bot_message_quota_consumption::execute(
+            &self.channel_access_token,
+            &self.options,
+        )
+        .await?;
+        // 取り直した使用数にはそれまでに送信済みの分が含まれるので、数えた分はリセットする
+        // (送信中の分は含まれているとは限らないので in_flight はそのまま)
+        self.lock().cached = Some(QuotaState {
+            limit: match quota.quota_type {
+                QuotaType::Limited => quota.value,
+                QuotaType::None => None,
+            },
+            total_usage: consumption.total_usage,
+            local_sent: 0,
+            fetched_at: Instant::now(),
+        });
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, GuardState> {
+        // ロック中に panic しても状態は壊れないので、poison は無視する
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn check_state(state: &GuardState, cost: u64) -> Result<(), Box<Error>> {
+    let Some(cached) = &state.cached else {
+        return Ok(());
+    };
+    match (cached.limit, state.remaining()) {
+        (Some(limit), Some(remaining)) if cost as i64 > remaining => {
+            Err(Box::new(Error::QuotaExceeded(QuotaExceeded {
+                cost,
+                remaining,
+                limit,
+            })))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// multicast の送信見込み数(宛先数)。
+pub fn multicast_cost(to: &[String]) -> u64 {
+    to.len() as u64
+}
+
+/// broadcast の送信見込み数。日本時間の昨日の配信対象数(`targetedReaches`)を使い、
+/// 取得できない場合は友だち数で代用する。
+///
+/// 統計情報がまだ集計中(`status` が `ready` 以外)でどちらも取得できない場合は `Ok(None)`。
+/// その場合の見積もりは呼び出し側で決める。
+pub async fn broadcast_cost(
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<Option<u64>, Box<Error>> {
+    let (response, _) = get_v2_bot_insight_followers::execute(
+        &get_v2_bot_insight_followers::QueryParams::yesterday(),
+        channel_access_token,
+        options,
+    )
+    .await?;
+    Ok(response.targeted_reaches.or(response.followers))
+}
@@ -0,0 +1,680 @@
+//! メッセージオブジェクトのローカル検証。
+//!
+//! `/v2/bot/message/validate/push` を呼ばずに、LINE のリファレンスに書かれている制約
+//! (テキストの文字数、URL のスキームと長さ、スタンプ ID、`altText` の文字数、アクションの
+//! ラベルの文字数、Flex Message の JSON サイズなど)を手元で確認する。
+//!
+//! 結果はサーバーの `details` と同じ [`ErrorDetail`](`message` と `property`)の一覧で返す。
+//! `property` も `messages[0].template.actions[1].label` のようにサーバーと同じ形式になる。
+//! 空なら問題無し。
+//!
+//! ここで通っても LINE 側で弾かれることはある(画像の実在や Flex のレイアウトなどは確認しない)。
+//! 文字数は Unicode のスカラー値で数えるので、絵文字などで LINE の数え方と差が出ることがある。
+//! 知らない `type` のメッセージは共通項目以外確認しない。
+//!
+//! LINE 側で確認したい場合(リリース前にテンプレートを CI で確かめるなど)は、送信種別ごとの
+//! `/v2/bot/message/validate/*` を呼ぶ [`validate`] を使う。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::Display;
+
+use crate::{
+    LineOptions,
+    error::{Error, ErrorDetail, ErrorKind},
+    messaging_api::{
+        post_v2_bot_message_validate_broadcast, post_v2_bot_message_validate_multicast,
+        post_v2_bot_message_validate_narrowcast, post_v2_bot_message_validate_push,
+        post_v2_bot_message_validate_reply,
+    },
+};
+
+/// 1 リクエストで送れるメッセージ数の上限
+pub const MAX_MESSAGES: usize = 5;
+/// 画像・動画などの URL の最大文字数
+pub const MAX_URL_LENGTH: usize = 2000;
+/// Flex Message のバブル 1 つの JSON の最大サイズ(バイト)
+pub const MAX_FLEX_BUBBLE_SIZE: usize = 30 * 1024;
+/// Flex Message のカルーセル全体の JSON の最大サイズ(バイト)
+pub const MAX_FLEX_CAROUSEL_SIZE: usize = 50 * 1024;
+
+const CONTENT_URL_SCHEMES: &[&str] = &["https"];
+const ACTION_URI_SCHEMES: &[&str] = &["http", "https", "line", "tel"];
+
+/// 送信エンドポイントの `messages` をまとめて検証する(件数は 1〜[`MAX_MESSAGES`])。
+pub fn validate_messages(messages: &[Value]) -> Vec<ErrorDetail> {
+    let mut checker = Checker::default();
+    checker.size("messages", messages.len(), 1, MAX_MESSAGES);
+    for (i, message) in messages.iter().enumerate() {
+        checker.message(&format!("messages[{i}]"), message);
+    }
+    checker.details
+}
+
+/// メッセージ 1 件を検証する。`property` は `property_prefix` からの相対パスになる。
+pub fn validate_message(property_prefix: &str, message: &Value) -> Vec<ErrorDetail> {
+    let mut checker = Checker::default();
+    checker.message(property_prefix, message);
+    checker.details
+}
+
+/// [`validate`] で検証する送信種別。
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Display, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum MessageKind {
+    Reply,
+    Push,
+    Multicast,
+    Narrowcast,
+    Broadcast,
+}
+
+/// `kind` の送信で `messages` が受け付けられるかを LINE の検証エンドポイントで確認する。
+///
+/// 問題が無ければ `Ok(Ok(()))`、検証で弾かれた(400)場合は `Ok(Err(details))` を返す。
+/// `details` の無い 400 は `message` だけを持つ(`property` が空の)1 件にする。
+/// 認証エラーや通信エラーなど、検証以外のエラーは `Err` で返す。
+pub async fn validate(
+    kind: MessageKind,
+    messages: Vec<Value>,
+    channel_access_token: &str,
+    options: &LineOptions,
+) -> Result<Result<(), Vec<ErrorDetail>>, Box<Error>> {
+    let result = match kind {
+        MessageKind::Reply => {
+            post_v2_bot_message_validate_reply::execute(
+                post_v2_bot_message_validate_reply::RequestBody { messages },
+                channel_access_token,
+                options,
+            )
+            .await
+        }
+        MessageKind::Push => {
+            post_v2_bot_message_validate_push::execute(
+                post_v2_bot_message_validate_push::RequestBody { messages },
+                channel_access_token,
+                options,
+            )
+            .await
+        }
+        MessageKind::Multicast => {
+            post_v2_bot_message_validate_multicast::execute(
+                post_v2_bot_message_validate_multicast::RequestBody { messages },
+                channel_access_token,
+                options,
+            )
+            .await
+        }
+        MessageKind::Narrowcast => {
+            post_v2_bot_message_validate_narrowcast::execute(
+                post_v2_bot_message_validate_narrowcast::RequestBody { messages },
+                channel_access_token,
+                options,
+            )
+            .await
+        }
+        MessageKind::Broadcast => {
+            post_v2_bot_message_validate_broadcast::execute(
+                post_v2_bot_message_validate_broadcast::RequestBody { messages },
+                channel_access_token,
+                options,
+            )
+            .await
+        }
+    };
+    match result {
+        Ok(_) => Ok(Ok(())),
+        Err(err) if err.kind() == ErrorKind::BadRequest => match *err {
+            Error::Line(response, _, _) => Ok(Err(response
+                .details
+                .filter(|details| !details.is_empty())
+                .unwrap_or_else(|| {
+                    vec![ErrorDetail {
+                        message: response.message,
+                        property: String::new(),
+                    }]
+                }))),
+            other => Err(Box::new(other)),
+        },
+        Err(err) => Err(err),
+    }
+}
+
+#[derive(Debug, Default)]
+struct Checker {
+    details: Vec<ErrorDetail>,
+}
+
+impl Checker {
+    fn push(&mut self, property: &str, message: impl Into<String>) {
+        self.details.push(ErrorDetail {
+            message: message.into(),
+            property: property.to_owned(),
+        });
+    }
+
+    fn size(&mut self, property: &str, size: usize, min: usize, max: usize) {
+        if !(min..=max).contains(&size) {
+            self.push(property, format!("Size must be between {min} and {max}"));
+        }
+    }
+
+    // 必須の文字列。無ければ記録して None
+    fn required_str<'a>(&mut self, value: &'a Value, property: &str) -> Option<&'a str> {
+        let text = value.as_str();
+        if text.is_none() {
+            self.push(property, "must be specified");
+        }
+        text
+    }
+
+    fn text(&mut self, value: &Value, property: &str, min: usize, max: usize) {
+        if let Some(text) = self.required_str(value, property) {
+            self.length(text, property, min, max);
+        }
+    }
+
+    fn optional_text(&mut self, value: &Value, property: &str, max: usize) {
+        if let Some(text) = value.as_str() {
+            self.length(text, property, 0, max);
+        }
+    }
+
+    fn length(&mut self, text: &str, property: &str, min: usize, max: usize) {
+        if !(min..=max).contains(&text.chars().count()) {
+            self.push(property, format!("Length must be between {min} and {max}"));
+        }
+    }
+
+    fn url(&mut self, value: &Value, property: &str, schemes: &[&str], max: usize) {
+        if let Some(url) = self.required_str(value, property) {
+            self.url_str(url, property, schemes, max);
+        }
+    }
+
+    fn optional_url(&mut self, value: &Value, property: &str, schemes: &[&str], max: usize) {
+        if let Some(url) = value.as_str() {
+            self.url_str(url, property, schemes, max);
+        }
+    }
+
+    fn url_str(&mut self, url: &str, property: &str, schemes: &[&str], max: usize) {
+        self.length(url, property, 1, max);
+        let scheme = url
+            .split_once(':')
+            .map(|(scheme, _)| scheme.to_ascii_lowercase());
+        if !scheme.is_some_and(|scheme| schemes.contains(&scheme.as_str())) {
+            self.push(
+                property,
+                format!("URL scheme must be one of {}", schemes.join(", ")),
+            );
+        }
+    }
+
+    fn array<'a>(
+        &mut self,
+        value: &'a Value,
+        property: &str,
+        min: usize,
+        max: usize,
+    ) -> &'a [Value] {
+        match value.as_array() {
+            Some(items) => {
+                self.size(property, items.len(), min, max);
+                items
+            }
+            None => {
+                self.push(property, "must be specified");
+                &[]
+            }
+        }
+    }
+
+    fn message(&mut self, prefix: &str, message: &Value) {
+        let Some(message_type) = self.required_str(&message["type"], &format!("{prefix}.type"))
+        else {
+            return;
+        };
+        match message_type {
+            "text" | "textV2" => self.text(&message["text"], &format!("{prefix}.text"), 1, 5000),
+            "sticker" => {
+                for key in ["packageId", "stickerId"] {
+                    let property = format!("{prefix}.{key}");
+                    if let Some(id) = self.required_str(&message[key], &property)
+                        && (id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()))
+                    {
+                        self.push(&property, "must be numeric");
+                    }
+                }
+            }
+            "image" | "video" => {
+                for key in ["originalContentUrl", "previewImageUrl"] {
+                    self.url(
+                        &message[key],
+                        &format!("{prefix}.{key}"),
+                        CONTENT_URL_SCHEMES,
+                        MAX_URL_LENGTH,
+                    );
+                }
+                self.optional_text(&message["trackingId"], &format!("{prefix}.trackingId"), 100);
+            }
+            "audio" => {
+                self.url(
+                    &message["originalContentUrl"],
+                    &format!("{prefix}.originalContentUrl"),
+                    CONTENT_URL_SCHEMES,
+                    MAX_URL_LENGTH,
+                );
+                if !message["duration"].is_u64() {
+                    self.push(&format!("{prefix}.duration"), "must be specified");
+                }
+            }
+            "location" => {
+                self.text(&message["title"], &format!("{prefix}.title"), 1, 100);
+                self.text(&message["address"], &format!("{prefix}.address"), 1, 100);
+                for key in ["latitude", "longitude"] {
+                    if !message[key].is_number() {
+                        self.push(&format!("{prefix}.{key}"), "must be specified");
+                    }
+                }
+            }
+            "imagemap" => self.imagemap(prefix, message),
+            "template" => {
+                self.text(&message["altText"], &format!("{prefix}.altText"), 1, 400);
+                self.template(&format!("{prefix}.template"), &message["template"]);
+            }
+            "flex" => {
+                self.text(&message["altText"], &format!("{prefix}.altText"), 1, 1500);
+                self.flex_contents(&format!("{prefix}.contents"), &message["contents"]);
+            }
+            _ => {}
+        }
+        self.sender(&format!("{prefix}.sender"), &message["sender"]);
+        if !message["quickReply"].is_null() {
+            let property = format!("{prefix}.quickReply.items");
+            let items = self.array(&message["quickReply"]["items"], &property, 1, 13);
+            for (i, item) in items.iter().enumerate() {
+                let property = format!("{property}[{i}]");
+                self.optional_url(
+                    &item["imageUrl"],
+                    &format!("{property}.imageUrl"),
+                    CONTENT_URL_SCHEMES,
+                    1000,
+                );
+                self.action(&format!("{property}.action"), &item["action"], 20);
+            }
+        }
+    }
+
+    fn sender(&mut self, prefix: &str, sender: &Value) {
+        if sender.is_null() {
+            return;
+        }
+        self.optional_text(&sender["name"], &format!("{prefix}.name"), 20);
+        self.optional_url(
+            &sender["iconUrl"],
+            &format!("{prefix}.iconUrl"),
+            CONTENT_URL_SCHEMES,
+            1000,
+        );
+    }
+
+    fn imagemap(&mut self, prefix: &str, message: &Value) {
+        self.url(
+            &message["baseUrl"],
+            &format!("{prefix}.baseUrl"),
+            CONTENT_URL_SCHEMES,
+            MAX_URL_LENGTH,
+        );
+        self.text(&message["altText"], &format!("{prefix}.altText"), 1, 1500);
+        if !message["baseSize"].is_object() {
+            self.push(&format!("{prefix}.baseSize"), "must be specified");
+        }
+        let property = format!("{prefix}.actions");
+        for (i, action) in self
+            .array(&message["actions"], &property, 1, 50)
+            .iter()
+            .enumerate()
+        {
+            let property = format!("{property}[{i}]");
+            self.optional_text(&action["label"], &format!("{property}.label"), 50);
+            match action["type"].as_str() {
+                Some("uri") => self.url(
+                    &action["linkUri"],
+                    &format!("{property}.linkUri"),
+                    ACTION_URI_SCHEMES,
+                    1000,
+                ),
+                Some("message") => self.text(&action["text"], &format!("{property}.text"), 1, 400),
+                Some("clipboard") => self.text(
+                    &action["clipboardText"],
+                    &format!("{property}.clipboardText"),
+                    1,
+                    1000,
+                ),
+                _ => self.push(&format!("{property}.type"), "must be specified"),
+            }
+        }
+    }
+
+    fn template(&mut self, prefix: &str, template: &Value) {
+        let Some(template_type) = self.required_str(&template["type"], &format!("{prefix}.type"))
+        else {
+            return;
+        };
+        match template_type {
+            "buttons" => {
+                self.column(prefix, template, 160, 4);
+            }
+            "confirm" => {
+                self.text(&template["text"], &format!("{prefix}.text"), 1, 240);
+                let property = format!("{prefix}.actions");
+                for (i, action) in self
+                    .array(&template["actions"], &property, 2, 2)
+                    .iter()
+                    .enumerate()
+                {
+                    self.action(&format!("{property}[{i}]"), action, 20);
+                }
+            }
+            "carousel" => {
+                let property = format!("{prefix}.columns");
+                for (i, column) in self
+                    .array(&template["columns"], &property, 1, 10)
+                    .iter()
+                    .enumerate()
+                {
+                    self.column(&format!("{property}[{i}]"), column, 120, 3);
+                }
+            }
+            "image_carousel" => {
+                let property = format!("{prefix}.columns");
+                for (i, column) in self
+                    .array(&template["columns"], &property, 1, 10)
+                    .iter()
+                    .enumerate()
+                {
+                    let property = format!("{property}[{i}]");
+                    self.url(
+                        &column["imageUrl"],
+                        &format!("{property}.imageUrl"),
+                        CONTENT_URL_SCHEMES,
+                        MAX_URL_LENGTH,
+                    );
+                    // 画像カルーセルのラベルは 12 文字まで
+                    self.action(&format!("{property}.action"), &column["action"], 12);
+                }
+            }
+            _ => self.push(&format!("{prefix}.type"), "unknown template type"),
+        }
+    }
+
+    // ボタンテンプレートとカルーセルの列。画像かタイトルがあるとテキストは 60 文字まで
+    fn column(&mut self, prefix: &str, column: &Value, max_text: usize, max_actions: usize) {
+        self.optional_url(
+            &column["thumbnailImageUrl"],
+            &format!("{prefix}.thumbnailImageUrl"),
+            CONTENT_URL_SCHEMES,
+            MAX_URL_LENGTH,
+        );
+        self.optional_text(&column["title"], &format!("{prefix}.title"), 40);
+        let max_text = if column["thumbnailImageUrl"].is_string() || column["title"].is_string() {
+            60
+        } else {
+            max_text
+        };
+        self.text(&column["text"], &format!("{prefix}.text"), 1, max_text);
+        if !column["defaultAction"].is_null() {
+            self.action(
+                &format!("{prefix}.defaultAction"),
+                &column["defaultAction"],
+                20,
+            );
+        }
+        let property = format!("{prefix}.actions");
+        for (i, action) in self
+            .array(&column["actions"], &property, 1, max_actions)
+            .iter()
+            .enumerate()
+        {
+            self.action(&format!("{property}[{i}]"), action, 20);
+        }
+    }
+
+    // https://developers.line.biz/ja/reference/messaging-api/#action-objects
+    fn action(&mut self, prefix: &str, action: &Value, max_label: usize) {
+        let Some(action_type) = self.required_str(&action["type"], &format!("{prefix}.type"))
+        else {
+            return;
+        };
+        self.optional_text(&action["label"], &format!("{prefix}.label"), max_label);
+        match action_type {
+            "uri" => {
+                self.url(
+                    &action["uri"],
+                    &format!("{prefix}.uri"),
+                    ACTION_URI_SCHEMES,
+                    1000,
+                );
+                self.optional_url(
+                    &action["altUri"]["desktop"],
+                    &format!("{prefix}.altUri.desktop"),
+                    ACTION_URI_SCHEMES,
+                    1000,
+                );
+            }
+            "postback" => {
+                self.text(&action["data"], &format!("{prefix}.data"), 1, 300);
+                self.optional_text(
+                    &action["displayText"],
+                    &format!("{prefix}.displayText"),
+                    300,
+                );
+                self.optional_text(&action["text"], &format!("{prefix}.text"), 300);
+            }
+            "message" => self.text(&action["text"], &format!("{prefix}.text"), 1, 300),
+            "datetimepicker" => {
+                self.text(&action["data"], &format!("{prefix}.data"), 1, 300);
+                self.required_str(&action["mode"], &format!("{prefix}.mode"));
+            }
+            "clipboard" => self.text(
+                &action["clipboardText"],
+                &format!("{prefix}.clipboardText"),
+                1,
+                1000,
+            ),
+            "richmenuswitch" => {
+                self.text(
+                    &action["richMenuAliasId"],
+                    &format!("{prefix}.richMenuAliasId"),
+                    1,
+                    32,
+                );
+                self.text(&action["data"], &format!("{prefix}.data"), 1, 300);
+            }
+            _ => {}
+        }
+    }
+
+    fn flex_contents(&mut self, property: &str, contents: &Value) {
+        let Some(contents_type) = contents["type"].as_str() else {
+            self.push(property, "must be specified");
+            return;
+        };
+        let (max_size, bubbles) = match contents_type {
+            "carousel" => (
+                MAX_FLEX_CAROUSEL_SIZE,
+                self.array(
+                    &contents["contents"],
+                    &format!("{property}.contents"),
+                    1,
+                    12,
+                ),
+            ),
+            _ => (MAX_FLEX_BUBBLE_SIZE, std::slice::from_ref(contents)),
+        };
+        if json_size(contents) > max_size {
+            self.push(
+                property,
+                format!("JSON size must be at most {max_size} bytes"),
+            );
+        }
+        if contents_type == "carousel" {
+            for (i, bubble) in bubbles.iter().enumerate() {
+                if json_size(bubble) > MAX_FLEX_BUBBLE_SIZE {
+                    self.push(
+                        &format!("{property}.contents[{i}]"),
+                        format!("JSON size must be at most {MAX_FLEX_BUBBLE_SIZE} bytes"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn json_size(value: &Value) -> usize {
+    // Value のシリアライズは失敗しない
+    serde_json::to_vec(value)
+        .map(|it| it.len())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn properties(details: &[ErrorDetail]) -> Vec<&str> {
+        details.iter().map(|it| it.property.as_str()).collect()
+    }
+
+    #[test]
+    fn test_validate_messages_valid() {
+        let messages = vec![
+            json!({"type": "text", "text": "Hello", "sender": {"name": "Bot"}}),
+            json!({"type": "sticker", "packageId": "446", "stickerId": "1988"}),
+            json!({
+                "type": "image",
+                "originalContentUrl": "https://example.com/a.png",
+                "previewImageUrl": "https://example.com/b.png",
+                "quickReply": {"items": [
+                    {"type": "action", "action": {"type": "message", "label": "Yes", "text": "Yes"}}
+                ]}
+            }),
+            json!({
+                "type": "template",
+                "altText": "confirm",
+                "template": {
+                    "type": "confirm",
+                    "text": "Are you sure?",
+                    "actions": [
+                        {"type": "message", "label": "Yes", "text": "yes"},
+                        {"type": "uri", "label": "Open", "uri": "https://example.com"}
+                    ]
+                }
+            }),
+            json!({
+                "type": "flex",
+                "altText": "flex",
+                "contents": {"type": "bubble", "body": {"type": "box", "layout": "vertical", "contents": []}}
+            }),
+        ];
+        assert!(validate_messages(&messages).is_empty());
+    }
+
+    #[test]
+    fn test_validate_messages_invalid() {
+        let details = validate_messages(&[
+            json!({"type": "text", "text": "a".repeat(5001)}),
+            json!({"type": "sticker", "packageId": "abc"}),
+            json!({
+                "type": "image",
+                "originalContentUrl": "http://example.com/a.png",
+                "previewImageUrl": format!("https://example.com/{}", "a".repeat(2000))
+            }),
+            json!({
+                "type": "template",
+                "altText": "a".repeat(401),
+                "template": {
+                    "type": "buttons",
+                    "title": "Menu",
+                    "text": "a".repeat(61),
+                    "actions": [
+                        {"type": "postback", "label": "a".repeat(21), "data": "x"},
+                        {"type": "uri", "label": "Open", "uri": "javascript:alert(1)"}
+                    ]
+                }
+            }),
+            json!({"type": "flex", "altText": "flex", "contents": {
+                "type": "bubble",
+                "body": {"type": "text", "text": "a".repeat(MAX_FLEX_BUBBLE_SIZE)}
+            }}),
+        ]);
+        assert_eq!(
+            properties(&details),
+            vec![
+                "messages[0].text",
+                "messages[1].packageId",
+                "messages[1].stickerId",
+                "messages[2].originalContentUrl",
+                "messages[2].previewImageUrl",
+                "messages[3].altText",
+                "messages[3].template.text",
+                "messages[3].template.actions[0].label",
+                "messages[3].template.actions[1].uri",
+                "messages[4].contents",
+            ]
+        );
+        assert_eq!(details[0].message, "Length must be between 1 and 5000");
+        assert_eq!(details[2].message, "must be specified");
+
+        // イメージマップのアクションのラベルは 50 文字まで
+        let imagemap = |label: String| {
+            json!({
+                "type": "imagemap",
+                "baseUrl": "https://example.com/bot/images/rm001",
+                "altText": "imagemap",
+                "baseSize": {"width": 1040, "height": 1040},
+                "actions": [{
+                    "type": "message",
+                    "label": label,
+                    "text": "Hello",
+                    "area": {"x": 0, "y": 0, "width": 520, "height": 1040}
+                }]
+            })
+        };
+        assert!(validate_messages(&[imagemap("a".repeat(50))]).is_empty());
+        let details = validate_messages(&[imagemap("a".repeat(51))]);
+        assert_eq!(properties(&details), vec!["messages[0].actions[0].label"]);
+        assert_eq!(details[0].message, "Length must be between 0 and 50");
+    }
+
+    #[test]
+    fn test_validate_messages_count() {
+        let details = validate_messages(&[]);
+        assert_eq!(properties(&details), vec!["messages"]);
+        let message = json!({"type": "text", "text": "Hello"});
+        assert_eq!(validate_messages(&vec![message; 6]).len(), 1);
+    }
+
+    #[test]
+    fn test_validate_message_prefix() {
+        let details = validate_message(
+            "message",
+            &json!({
+                "type": "template",
+                "altText": "carousel",
+                "template": {"type": "image_carousel", "columns": [{
+                    "imageUrl": "https://example.com/a.png",
+                    "action": {"type": "message", "label": "a".repeat(13), "text": "x"}
+                }]}
+            }),
+        );
+        assert_eq!(
+            properties(&details),
+            vec!["message.template.columns[0].action.label"]
+        );
+    }
+}
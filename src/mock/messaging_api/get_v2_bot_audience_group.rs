@@ -0,0 +1,159 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub audience_group_id: i64,
+    pub description: String,
+    pub status: String,
+    pub audience_count: u64,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.audience_group_id.is_none() {
+        builder.audience_group_id(1234567890123i64);
+    }
+    if builder.description.is_none() {
+        builder.description("test audience".to_string());
+    }
+    if builder.status.is_none() {
+        builder.status("READY".to_string());
+    }
+    if builder.audience_count.is_none() {
+        builder.audience_count(2u64);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        json!({
+            "audienceGroup": {
+                "audienceGroupId": params.audience_group_id,
+                "createRoute": "MESSAGING_API",
+                "type": "UPLOAD",
+                "description": params.description,
+                "status": params.status,
+                "audienceCount": params.audience_count,
+                "created": 1613698278,
+                "permission": "READ_WRITE",
+                "isIfaAudience": false,
+                "expireTimestamp": 1629250278
+            },
+            "jobs": [
+                {
+                    "audienceGroupJobId": 12345678,
+                    "audienceGroupId": params.audience_group_id,
+                    "description": "audience_list.txt",
+                    "type": "DIFF_ADD",
+                    "jobStatus": "FINISHED",
+                    "audienceCount": params.audience_count,
+                    "created": 1613698278
+                }
+            ]
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock(
+            "GET",
+            format!("/v2/bot/audienceGroup/{}", params.audience_group_id).as_str(),
+        )
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{
+            audience_group::{AudienceGroupJobStatus, AudienceGroupStatus},
+            get_v2_bot_audience_group,
+        },
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status("IN_PROGRESS".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_audience_group::execute(
+            1234567890123,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.audience_group.audience_group_id, 1234567890123);
+        assert_eq!(res.0.audience_group.status, AudienceGroupStatus::InProgress);
+        assert_eq!(res.0.jobs.len(), 1);
+        assert_eq!(res.0.jobs[0].job_status, AudienceGroupJobStatus::Finished);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(404usize);
+        builder.error_message("Audience group not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_audience_group::execute(
+            1234567890123,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 404);
+                    assert_eq!(response.message, "Audience group not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
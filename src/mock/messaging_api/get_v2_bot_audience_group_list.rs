@@ -0,0 +1,195 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub page: u64,
+    pub size: u64,
+    pub audience_group_ids: Vec<i64>,
+    pub has_next_page: bool,
+    pub total_count: u64,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.page.is_none() {
+        builder.page(1u64);
+    }
+    if builder.size.is_none() {
+        builder.size(40u64);
+    }
+    if builder.audience_group_ids.is_none() {
+        builder.audience_group_ids(vec![1111i64, 2222i64]);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        let audience_groups: Vec<_> = params
+            .audience_group_ids
+            .iter()
+            .map(|id| {
+                json!({
+                    "audienceGroupId": id,
+                    "createRoute": "MESSAGING_API",
+                    "type": "UPLOAD",
+                    "description": format!("audience {id}"),
+                    "status": "READY",
+                    "audienceCount": 2,
+                    "created": 1613698278,
+                    "permission": "READ_WRITE",
+                    "isIfaAudience": false,
+                    "expireTimestamp": 1629250278
+                })
+            })
+            .collect();
+        json!({
+            "audienceGroups": audience_groups,
+            "hasNextPage": params.has_next_page,
+            "totalCount": params.total_count.max(params.audience_group_ids.len() as u64),
+            "readWriteAudienceGroupTotalCount": params.audience_group_ids.len(),
+            "page": params.page,
+            "size": params.size
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock("GET", "/v2/bot/audienceGroup/list")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("page".to_string(), params.page.to_string()),
+            Matcher::UrlEncoded("size".to_string(), params.size.to_string()),
+        ]))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{audience_group::AudienceGroupStatus, get_v2_bot_audience_group_list},
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_list_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_list_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let res = get_v2_bot_audience_group_list::execute(
+            &get_v2_bot_audience_group_list::QueryParams::new(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.audience_groups.len(), 2);
+        assert_eq!(res.0.audience_groups[0].status, AudienceGroupStatus::Ready);
+        assert!(!res.0.has_next_page);
+        assert_eq!(res.0.total_count, 2);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_list_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_list_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(401usize);
+        builder.error_message("Authentication failed".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_audience_group_list::execute(
+            &get_v2_bot_audience_group_list::QueryParams::new(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 401);
+                    assert_eq!(response.message, "Authentication failed");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // hasNextPage を見て page を進めること、max_page_count で打ち切られることを確認する。
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_list_stream -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_list_stream() {
+        for (max_page_count, expected) in [(0, vec![1111, 2222]), (100, vec![1111, 2222, 3333])] {
+            let mut server = Server::new_async().await;
+            let mut builder = MockParamsBuilder::default();
+            builder.has_next_page(true);
+            builder.total_count(3u64);
+            let page1 = make_mock(&mut server, Some(builder)).await;
+            let mut builder = MockParamsBuilder::default();
+            builder.page(2u64);
+            builder.audience_group_ids(vec![3333i64]);
+            builder.total_count(3u64);
+            let page2 = make_mock(&mut server, Some(builder)).await;
+
+            let res = get_v2_bot_audience_group_list::execute_stream(
+                get_v2_bot_audience_group_list::QueryParams::new(),
+                "test_channel_access_token",
+                &LineOptions::builder().with_prefix_url(server.url()).build(),
+                max_page_count,
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                res.iter()
+                    .map(|it| it.audience_group_id)
+                    .collect::<Vec<_>>(),
+                expected
+            );
+
+            page1.assert_async().await;
+            // max_page_count=0 なら 1 ページ目で打ち切られる
+            if max_page_count == 0 {
+                assert!(!page2.matched_async().await);
+            } else {
+                page2.assert_async().await;
+            }
+        }
+    }
+}
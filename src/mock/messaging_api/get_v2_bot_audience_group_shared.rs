@@ -0,0 +1,157 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub audience_group_id: i64,
+    pub description: String,
+    pub owner_id: String,
+    pub owner_name: String,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.audience_group_id.is_none() {
+        builder.audience_group_id(1234567890123i64);
+    }
+    if builder.description.is_none() {
+        builder.description("shared audience".to_string());
+    }
+    if builder.owner_id.is_none() {
+        builder.owner_id("U1234567890abcdef".to_string());
+    }
+    if builder.owner_name.is_none() {
+        builder.owner_name("Business Manager".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        json!({
+            "audienceGroup": {
+                "audienceGroupId": params.audience_group_id,
+                "createRoute": "AD_MANAGER",
+                "type": "UPLOAD",
+                "description": params.description,
+                "status": "READY",
+                "audienceCount": 1000,
+                "created": 1613698278,
+                "permission": "READ",
+                "isIfaAudience": false,
+                "expireTimestamp": 1629250278
+            },
+            "jobs": [],
+            "owner": {
+                "serviceType": "bm",
+                "id": params.owner_id,
+                "name": params.owner_name
+            }
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock(
+            "GET",
+            format!("/v2/bot/audienceGroup/shared/{}", params.audience_group_id).as_str(),
+        )
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{
+            audience_group::AudienceGroupPermission, get_v2_bot_audience_group_shared,
+        },
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_shared_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_shared_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let res = get_v2_bot_audience_group_shared::execute(
+            1234567890123,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.audience_group.audience_group_id, 1234567890123);
+        assert_eq!(
+            res.0.audience_group.permission,
+            Some(AudienceGroupPermission::Read)
+        );
+        assert!(res.0.jobs.is_empty());
+        let owner = res.0.owner.unwrap();
+        assert_eq!(owner.service_type, "bm");
+        assert_eq!(owner.id, "U1234567890abcdef");
+        assert_eq!(owner.name.as_deref(), Some("Business Manager"));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_shared_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_shared_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(404usize);
+        builder.error_message("Audience group not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_audience_group_shared::execute(
+            1234567890123,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 404);
+                    assert_eq!(response.message, "Audience group not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,229 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub page: u64,
+    pub size: u64,
+    // 共有されているオーディエンス(permission=READ)
+    pub audience_group_ids: Vec<i64>,
+    // includesOwnedAudienceGroups=true のときだけ返す自分のオーディエンス(permission=READ_WRITE)
+    pub owned_audience_group_ids: Vec<i64>,
+    pub includes_owned_audience_groups: bool,
+    pub has_next_page: bool,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.page.is_none() {
+        builder.page(1u64);
+    }
+    if builder.size.is_none() {
+        builder.size(40u64);
+    }
+    if builder.audience_group_ids.is_none() {
+        builder.audience_group_ids(vec![5555i64, 6666i64]);
+    }
+    if builder.owned_audience_group_ids.is_none() {
+        builder.owned_audience_group_ids(vec![1111i64]);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        let make_group = |id: &i64, permission: &str| {
+            json!({
+                "audienceGroupId": id,
+                "createRoute": "AD_MANAGER",
+                "type": "UPLOAD",
+                "description": format!("audience {id}"),
+                "status": "READY",
+                "audienceCount": 1000,
+                "created": 1613698278,
+                "permission": permission,
+                "isIfaAudience": false,
+                "expireTimestamp": 1629250278
+            })
+        };
+        let mut audience_groups: Vec<_> = params
+            .audience_group_ids
+            .iter()
+            .map(|id| make_group(id, "READ"))
+            .collect();
+        let mut read_write_count = 0;
+        if params.includes_owned_audience_groups {
+            audience_groups.extend(
+                params
+                    .owned_audience_group_ids
+                    .iter()
+                    .map(|id| make_group(id, "READ_WRITE")),
+            );
+            read_write_count = params.owned_audience_group_ids.len();
+        }
+        json!({
+            "totalCount": audience_groups.len(),
+            "audienceGroups": audience_groups,
+            "hasNextPage": params.has_next_page,
+            "readWriteAudienceGroupTotalCount": read_write_count,
+            "page": params.page,
+            "size": params.size
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut query = vec![
+        Matcher::UrlEncoded("page".to_string(), params.page.to_string()),
+        Matcher::UrlEncoded("size".to_string(), params.size.to_string()),
+    ];
+    if params.includes_owned_audience_groups {
+        query.push(Matcher::UrlEncoded(
+            "includesOwnedAudienceGroups".to_string(),
+            "true".to_string(),
+        ));
+    }
+
+    server
+        .mock("GET", "/v2/bot/audienceGroup/shared/list")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_query(Matcher::AllOf(query))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{
+            audience_group::AudienceGroupPermission, get_v2_bot_audience_group_shared_list,
+        },
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_shared_list_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_shared_list_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let res = get_v2_bot_audience_group_shared_list::execute(
+            &get_v2_bot_audience_group_shared_list::QueryParams::new(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.audience_groups.len(), 2);
+        assert!(
+            res.0
+                .audience_groups
+                .iter()
+                .all(|it| it.permission == Some(AudienceGroupPermission::Read))
+        );
+        assert_eq!(res.0.read_write_audience_group_total_count, Some(0));
+
+        mock.assert_async().await;
+    }
+
+    // 共有オーディエンスと自分のオーディエンスを同じ型でまとめて取得できることを確認する。
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_shared_list_with_owned -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_shared_list_with_owned() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.includes_owned_audience_groups(true);
+        builder.has_next_page(true);
+        let page1 = make_mock(&mut server, Some(builder)).await;
+        let mut builder = MockParamsBuilder::default();
+        builder.includes_owned_audience_groups(true);
+        builder.page(2u64);
+        builder.audience_group_ids(vec![7777i64]);
+        builder.owned_audience_group_ids(vec![]);
+        let page2 = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_audience_group_shared_list::execute_stream(
+            get_v2_bot_audience_group_shared_list::QueryParams::with_owned(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+            100,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            res.iter()
+                .map(|it| (it.audience_group_id, it.permission.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (5555, Some(AudienceGroupPermission::Read)),
+                (6666, Some(AudienceGroupPermission::Read)),
+                (1111, Some(AudienceGroupPermission::ReadWrite)),
+                (7777, Some(AudienceGroupPermission::Read)),
+            ]
+        );
+
+        page1.assert_async().await;
+        page2.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_audience_group_shared_list_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_audience_group_shared_list_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(403usize);
+        builder.error_message("Not authorized to access shared audiences".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_audience_group_shared_list::execute(
+            &get_v2_bot_audience_group_shared_list::QueryParams::new(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 403);
+                    assert_eq!(
+                        response.message,
+                        "Not authorized to access shared audiences"
+                    );
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
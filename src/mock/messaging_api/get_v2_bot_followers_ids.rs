@@ -0,0 +1,179 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub start: Option<String>,
+    pub limit: Option<u16>,
+    pub status_code: usize,
+    pub user_ids: Vec<String>,
+    pub next: Option<String>,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.user_ids.is_none() {
+        builder.user_ids(vec!["U111".to_string(), "U222".to_string()]);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        let mut response = json!({
+            "userIds": params.user_ids,
+        });
+        if let Some(next) = params.next {
+            response["next"] = json!(next);
+        }
+        response
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut query = vec![];
+    if let Some(start) = params.start {
+        query.push(Matcher::UrlEncoded("start".to_string(), start));
+    }
+    if let Some(limit) = params.limit {
+        query.push(Matcher::UrlEncoded("limit".to_string(), limit.to_string()));
+    }
+
+    let mut mock_builder = server.mock("GET", "/v2/bot/followers/ids").match_header(
+        "authorization",
+        format!("Bearer {}", params.channel_access_token).as_str(),
+    );
+    if !query.is_empty() {
+        mock_builder = mock_builder.match_query(Matcher::AllOf(query));
+    }
+
+    mock_builder
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, messaging_api::get_v2_bot_followers_ids, option::LineOptions};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_followers_ids_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_followers_ids_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.limit(Some(1000u16));
+        builder.next(Some("next_token".to_string()));
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_followers_ids::QueryParams::new("");
+        let res = get_v2_bot_followers_ids::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.user_ids, vec!["U111", "U222"]);
+        assert_eq!(res.0.next, Some("next_token".to_string()));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_followers_ids_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_followers_ids_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.limit(Some(1000u16));
+        builder.status_code(403usize);
+        builder.error_message("Access to this API is not available".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_followers_ids::QueryParams::new("");
+        let res = get_v2_bot_followers_ids::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 403);
+                    assert_eq!(response.message, "Access to this API is not available");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // 2 ページを巡回して全件を返すこと、max_page_count で打ち切られることを確認する。
+    // cargo test --all-features test_make_mock_get_v2_bot_followers_ids_stream -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_followers_ids_stream() {
+        let mut server = Server::new_async().await;
+
+        let page1 = server
+            .mock("GET", "/v2/bot/followers/ids")
+            .match_query(Matcher::Exact("limit=1000".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"userIds": ["U1", "U2"], "next": "page2_token"}).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+        let page2 = server
+            .mock("GET", "/v2/bot/followers/ids")
+            .match_query(Matcher::Exact("start=page2_token&limit=1000".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"userIds": ["U3"]}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let options = LineOptions::builder().with_prefix_url(server.url()).build();
+
+        // max_page_count=0 なら 1 ページ目で打ち切られる
+        let res =
+            get_v2_bot_followers_ids::execute_stream("test_channel_access_token", &options, 0)
+                .await
+                .unwrap();
+        assert_eq!(res, vec!["U1", "U2"]);
+
+        let res =
+            get_v2_bot_followers_ids::execute_stream("test_channel_access_token", &options, 100)
+                .await
+                .unwrap();
+        assert_eq!(res, vec!["U1", "U2", "U3"]);
+
+        page1.assert_async().await;
+        page2.assert_async().await;
+    }
+}
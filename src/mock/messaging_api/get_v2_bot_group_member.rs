@@ -0,0 +1,140 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub group_id: String,
+    pub user_id: String,
+    pub status_code: usize,
+    pub display_name: String,
+    pub picture_url: Option<String>,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.group_id.is_none() {
+        builder.group_id("C123456789".to_string());
+    }
+    if builder.user_id.is_none() {
+        builder.user_id("U123456789".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.display_name.is_none() {
+        builder.display_name("Test User".to_string());
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        let mut response = json!({
+            "displayName": params.display_name,
+            "userId": params.user_id,
+        });
+        if let Some(picture_url) = params.picture_url {
+            response["pictureUrl"] = json!(picture_url);
+        }
+        response
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock(
+            "GET",
+            format!(
+                "/v2/bot/group/{}/member/{}",
+                params.group_id, params.user_id
+            )
+            .as_str(),
+        )
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, messaging_api::get_v2_bot_group_member, option::LineOptions};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_group_member_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_group_member_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.picture_url(Some("https://example.com/user.jpg".to_string()));
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_group_member::execute(
+            "C123456789",
+            "U123456789",
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.display_name, "Test User");
+        assert_eq!(res.0.user_id, "U123456789");
+        assert_eq!(
+            res.0.picture_url,
+            Some("https://example.com/user.jpg".to_string())
+        );
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_group_member_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_group_member_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(404usize);
+        builder.error_message("Not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_group_member::execute(
+            "C123456789",
+            "U123456789",
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 404);
+                    assert_eq!(response.message, "Not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,164 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub available: bool,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.available.is_none() {
+        builder.available(true);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        if params.available {
+            json!({
+                "available": true,
+                "genders": [
+                    {"gender": "unknown", "percentage": 37.6},
+                    {"gender": "male", "percentage": 31.8},
+                    {"gender": "female", "percentage": 30.6}
+                ],
+                "ages": [
+                    {"age": "unknown", "percentage": 37.6},
+                    {"age": "from50", "percentage": 17.3}
+                ],
+                "areas": [
+                    {"area": "unknown", "percentage": 42.9},
+                    {"area": "徳島", "percentage": 2.9}
+                ],
+                "appTypes": [
+                    {"appType": "ios", "percentage": 62.4},
+                    {"appType": "android", "percentage": 27.7},
+                    {"appType": "others", "percentage": 9.9}
+                ],
+                "subscriptionPeriods": [
+                    {"subscriptionPeriod": "over365days", "percentage": 96.4},
+                    {"subscriptionPeriod": "within365days", "percentage": 1.9}
+                ]
+            })
+        } else {
+            json!({
+                "available": false,
+            })
+        }
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock("GET", "/v2/bot/insight/demographic")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LineOptions, error::Error, messaging_api::get_v2_bot_insight_demographic};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_demographic_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_demographic_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let res = get_v2_bot_insight_demographic::execute(
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert!(res.0.available);
+        assert_eq!(res.0.genders.len(), 3);
+        assert_eq!(res.0.genders[1].gender, "male");
+        assert_eq!(res.0.app_types[0].app_type, "ios");
+        assert_eq!(
+            res.0.subscription_periods[0].subscription_period,
+            "over365days"
+        );
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_demographic_unavailable -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_demographic_unavailable() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.available(false);
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_insight_demographic::execute(
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!res.0.available);
+        assert!(res.0.genders.is_empty());
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_demographic_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_demographic_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(401usize);
+        builder.error_message("Authentication failed".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_insight_demographic::execute(
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 401);
+                    assert_eq!(response.message, "Authentication failed");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,278 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub date: String,
+    pub status: String,
+    pub followers: Option<u64>,
+    pub targeted_reaches: Option<u64>,
+    pub blocks: Option<u64>,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.date.is_none() {
+        builder.date("20240801".to_string());
+    }
+    if builder.status.is_none() {
+        builder.status("ready".to_string());
+    }
+    if builder.followers.is_none() {
+        builder.followers(Some(7620u64));
+    }
+    if builder.targeted_reaches.is_none() {
+        builder.targeted_reaches(Some(5848u64));
+    }
+    if builder.blocks.is_none() {
+        builder.blocks(Some(237u64));
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        if params.status == "ready" {
+            json!({
+                "status": params.status,
+                "followers": params.followers,
+                "targetedReaches": params.targeted_reaches,
+                "blocks": params.blocks,
+            })
+        } else {
+            // 集計前はカウンターが返らない
+            json!({
+                "status": params.status,
+            })
+        }
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock("GET", "/v2/bot/insight/followers")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_query(Matcher::UrlEncoded("date".to_string(), params.date))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        LineOptions,
+        error::Error,
+        messaging_api::{get_v2_bot_insight_followers, insight::InsightStatus},
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_followers_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_followers_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let query_params = get_v2_bot_insight_followers::QueryParams::new(
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        let res = get_v2_bot_insight_followers::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.status, InsightStatus::Ready);
+        assert_eq!(res.0.followers, Some(7620));
+        assert_eq!(res.0.targeted_reaches, Some(5848));
+        assert_eq!(res.0.blocks, Some(237));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_followers_out_of_service -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_followers_out_of_service() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status("out_of_service".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_insight_followers::QueryParams::new(
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        let res = get_v2_bot_insight_followers::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.status, InsightStatus::OutOfService);
+        assert_eq!(res.0.followers, None);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_followers_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_followers_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        builder.error_message("Invalid date".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_insight_followers::QueryParams::new(
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        let res = get_v2_bot_insight_followers::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "Invalid date");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // unready の日は取り直し、ユニット単位の統計も日ごとにまとめることを確認する。
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_followers_collect -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_followers_collect() {
+        use chrono::NaiveDate;
+
+        use crate::messaging_api::insight::{CollectOptions, collect};
+        use crate::mock::messaging_api::{
+            get_v2_bot_insight_message_delivery as delivery_mock,
+            get_v2_bot_insight_message_event_aggregation as aggregation_mock,
+        };
+
+        let mut server = Server::new_async().await;
+        let mut mocks = vec![];
+        for date in ["20240801", "20240802"] {
+            let mut builder = delivery_mock::MockParamsBuilder::default();
+            builder.date(date.to_string());
+            mocks.push(delivery_mock::make_mock(&mut server, Some(builder)).await);
+            let mut builder = aggregation_mock::MockParamsBuilder::default();
+            builder.from(date.to_string());
+            builder.to(date.to_string());
+            mocks.push(aggregation_mock::make_mock(&mut server, Some(builder)).await);
+        }
+        // 2 日目の配信数は取り直しでもう一度呼ばれる
+        let mut builder = delivery_mock::MockParamsBuilder::default();
+        builder.date("20240802".to_string());
+        mocks.push(delivery_mock::make_mock(&mut server, Some(builder)).await);
+        let mut builder = MockParamsBuilder::default();
+        builder.date("20240801".to_string());
+        let day1 = make_mock(&mut server, Some(builder)).await;
+        // 2 日目は 1 回目が unready、2 回目で ready になる
+        let mut builder = MockParamsBuilder::default();
+        builder.date("20240802".to_string());
+        builder.status("unready".to_string());
+        let day2_unready = make_mock(&mut server, Some(builder)).await;
+        let mut builder = MockParamsBuilder::default();
+        builder.date("20240802".to_string());
+        builder.followers(Some(7700u64));
+        let day2_ready = make_mock(&mut server, Some(builder)).await;
+
+        let from = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+        let res = collect(
+            from..=to,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+            &CollectOptions::new()
+                .with_concurrency(2)
+                .with_units(vec!["promotion_a".to_string()])
+                .with_unready_retry_duration(std::time::Duration::ZERO),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.keys().copied().collect::<Vec<_>>(), vec![from, to]);
+        assert_eq!(res[&from].followers.followers, Some(7620));
+        assert_eq!(res[&to].followers.followers, Some(7700));
+        assert_eq!(res[&to].to_row(to).followers, Some(7700));
+        assert_eq!(res[&to].to_row(to).broadcast, Some(5385));
+        let unit_rows = res[&to].unit_rows(to);
+        assert_eq!(unit_rows.len(), 1);
+        assert_eq!(unit_rows[0].unit, "promotion_a");
+        assert_eq!(unit_rows[0].unique_impression, Some(1000));
+
+        day1.assert_async().await;
+        day2_unready.assert_async().await;
+        day2_ready.assert_async().await;
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    // 取り直しても unready の日は結果に含まれないことを確認する。
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_followers_collect_unready -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_followers_collect_unready() {
+        use chrono::NaiveDate;
+
+        use crate::messaging_api::insight::{CollectOptions, collect};
+        use crate::mock::messaging_api::get_v2_bot_insight_message_delivery as delivery_mock;
+
+        let mut server = Server::new_async().await;
+        let delivery = delivery_mock::make_mock(&mut server, None).await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status("unready".to_string());
+        let followers = make_mock(&mut server, Some(builder)).await;
+
+        let date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let res = collect(
+            date..=date,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+            &CollectOptions::new().with_unready_retry_count(0),
+        )
+        .await
+        .unwrap();
+
+        assert!(res.is_empty());
+
+        delivery.assert_async().await;
+        followers.assert_async().await;
+    }
+}
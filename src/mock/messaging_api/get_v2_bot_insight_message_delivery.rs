@@ -0,0 +1,177 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub date: String,
+    pub status: String,
+    pub broadcast: Option<u64>,
+    pub api_push: Option<u64>,
+    pub api_reply: Option<u64>,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.date.is_none() {
+        builder.date("20240801".to_string());
+    }
+    if builder.status.is_none() {
+        builder.status("ready".to_string());
+    }
+    if builder.broadcast.is_none() {
+        builder.broadcast(Some(5385u64));
+    }
+    if builder.api_push.is_none() {
+        builder.api_push(Some(1234u64));
+    }
+    if builder.api_reply.is_none() {
+        builder.api_reply(Some(567u64));
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        if params.status == "ready" {
+            json!({
+                "status": params.status,
+                "broadcast": params.broadcast,
+                "targeting": 522,
+                "apiPush": params.api_push,
+                "apiReply": params.api_reply,
+            })
+        } else {
+            // 集計前はカウンターが返らない
+            json!({
+                "status": params.status,
+            })
+        }
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock("GET", "/v2/bot/insight/message/delivery")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_query(Matcher::UrlEncoded("date".to_string(), params.date))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        LineOptions,
+        error::Error,
+        messaging_api::{get_v2_bot_insight_message_delivery, insight::InsightStatus},
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_message_delivery_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_message_delivery_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let query_params = get_v2_bot_insight_message_delivery::QueryParams::new(
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        let res = get_v2_bot_insight_message_delivery::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.status, InsightStatus::Ready);
+        assert_eq!(res.0.broadcast, Some(5385));
+        assert_eq!(res.0.targeting, Some(522));
+        assert_eq!(res.0.api_push, Some(1234));
+        assert_eq!(res.0.api_multicast, None);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_message_delivery_unready -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_message_delivery_unready() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status("unready".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_insight_message_delivery::QueryParams::new(
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        let res = get_v2_bot_insight_message_delivery::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.status, InsightStatus::Unready);
+        assert_eq!(res.0.broadcast, None);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_message_delivery_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_message_delivery_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        builder.error_message("Invalid date".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_insight_message_delivery::QueryParams::new(
+            chrono::NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+        );
+        let res = get_v2_bot_insight_message_delivery::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "Invalid date");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
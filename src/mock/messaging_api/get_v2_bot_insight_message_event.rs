@@ -0,0 +1,170 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub request_id: String,
+    pub timestamp: i64,
+    pub delivered: Option<u64>,
+    pub unique_impression: Option<u64>,
+    pub unique_click: Option<u64>,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.request_id.is_none() {
+        builder.request_id("f70dd685-499a-4231-a441-f24b8d4fba21".to_string());
+    }
+    if builder.timestamp.is_none() {
+        builder.timestamp(1568214000i64);
+    }
+    if builder.delivered.is_none() {
+        builder.delivered(Some(32u64));
+    }
+    if builder.unique_impression.is_none() {
+        builder.unique_impression(Some(4u64));
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        json!({
+            "overview": {
+                "requestId": params.request_id,
+                "timestamp": params.timestamp,
+                "delivered": params.delivered,
+                "uniqueImpression": params.unique_impression,
+                "uniqueClick": params.unique_click,
+                "uniqueMediaPlayed": 2,
+                "uniqueMediaPlayed100Percent": null
+            },
+            "messages": [
+                {
+                    "seq": 1,
+                    "impression": 18,
+                    "mediaPlayed": 11,
+                    "mediaPlayed25Percent": null,
+                    "uniqueMediaPlayed": 2
+                }
+            ],
+            "clicks": [
+                {
+                    "seq": 1,
+                    "url": "https://www.yahoo.co.jp/",
+                    "click": null,
+                    "uniqueClick": null,
+                    "uniqueClickOfRequest": null
+                }
+            ]
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock("GET", "/v2/bot/insight/message/event")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_query(Matcher::UrlEncoded(
+            "requestId".to_string(),
+            params.request_id,
+        ))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LineOptions, error::Error, messaging_api::get_v2_bot_insight_message_event};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_message_event_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_message_event_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let query_params = get_v2_bot_insight_message_event::QueryParams::new(
+            "f70dd685-499a-4231-a441-f24b8d4fba21",
+        );
+        let res = get_v2_bot_insight_message_event::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            res.0.overview.request_id,
+            "f70dd685-499a-4231-a441-f24b8d4fba21"
+        );
+        assert_eq!(res.0.overview.delivered, Some(32));
+        assert_eq!(res.0.overview.unique_click, None);
+        assert_eq!(res.0.messages[0].impression, Some(18));
+        assert_eq!(res.0.messages[0].media_played_25_percent, None);
+        assert_eq!(
+            res.0.clicks[0].url,
+            Some("https://www.yahoo.co.jp/".to_string())
+        );
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_message_event_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_message_event_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(404usize);
+        builder.error_message("Not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_insight_message_event::QueryParams::new(
+            "f70dd685-499a-4231-a441-f24b8d4fba21",
+        );
+        let res = get_v2_bot_insight_message_event::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 404);
+                    assert_eq!(response.message, "Not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
@@ -192,6 +192,8 @@ pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>)
 
 #[cfg(test)]
 mod tests {
+    use chrono::NaiveDate;
+
     use crate::{
         LineOptions, error::Error, messaging_api::get_v2_bot_insight_message_event_aggregation,
     };
@@ -239,8 +241,8 @@ mod tests {
 
         let query_params = get_v2_bot_insight_message_event_aggregation::QueryParams {
             custom_aggregation_unit: "test_unit".to_string(),
-            from: "20240701".to_string(),
-            to: "20240731".to_string(),
+            from: NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2024, 7, 31).unwrap(),
         };
 
         let res = get_v2_bot_insight_message_event_aggregation::execute(
@@ -273,8 +275,8 @@ mod tests {
 
         let query_params = get_v2_bot_insight_message_event_aggregation::QueryParams {
             custom_aggregation_unit: "promotion_a".to_string(),
-            from: "20240801".to_string(),
-            to: "20240831".to_string(),
+            from: NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2024, 8, 31).unwrap(),
         };
 
         let res = get_v2_bot_insight_message_event_aggregation::execute(
@@ -297,4 +299,48 @@ mod tests {
 
         mock.assert_async().await;
     }
+
+    // 範囲外の期間は送信前にエラーになる(モックには届かない)。
+    // cargo test --all-features test_make_mock_get_v2_bot_insight_message_event_aggregation_invalid_range -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_insight_message_event_aggregation_invalid_range() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let from = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+        assert!(
+            get_v2_bot_insight_message_event_aggregation::QueryParams::with_range(
+                "promotion_a",
+                from,
+                to
+            )
+            .is_err()
+        );
+
+        let query_params = get_v2_bot_insight_message_event_aggregation::QueryParams {
+            custom_aggregation_unit: "promotion_a".to_string(),
+            from: to,
+            to: from,
+        };
+        let res = get_v2_bot_insight_message_event_aggregation::execute(
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Validation(validation_error) => {
+                    assert_eq!(validation_error.field, "from");
+                    assert_eq!(validation_error.actual, "2024-09-01");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        assert!(!mock.matched_async().await);
+    }
 }
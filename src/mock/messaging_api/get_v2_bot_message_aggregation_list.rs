@@ -326,4 +326,59 @@ mod tests {
             "on_response fires once per page"
         );
     }
+
+    // ユニット一覧を巡回し、各ユニットの統計を 1 つの表にまとめることを確認する。
+    // cargo test --all-features test_make_mock_get_v2_bot_message_aggregation_list_report -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_message_aggregation_list_report() {
+        use chrono::NaiveDate;
+
+        use crate::messaging_api::aggregation_report::AggregationReportBuilder;
+        use crate::mock::messaging_api::get_v2_bot_insight_message_event_aggregation as aggregation_mock;
+
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.limit(Some(100u8));
+        let list_mock = make_mock(&mut server, Some(builder)).await;
+        let mut unit_mocks = vec![];
+        for (unit, unique_impression) in [("promotion_a", 1000u64), ("promotion_b", 0u64)] {
+            let mut builder = aggregation_mock::MockParamsBuilder::default();
+            builder.custom_aggregation_unit(unit.to_string());
+            builder.unique_impression(Some(unique_impression));
+            unit_mocks.push(aggregation_mock::make_mock(&mut server, Some(builder)).await);
+        }
+
+        let report = AggregationReportBuilder::new()
+            .with_range(
+                NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 8, 31).unwrap(),
+            )
+            .unwrap()
+            .with_concurrency(2)
+            .execute(
+                "test_channel_access_token",
+                &LineOptions::builder().with_prefix_url(server.url()).build(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report
+                .rows
+                .iter()
+                .map(|it| it.unit.as_str())
+                .collect::<Vec<_>>(),
+            vec!["promotion_a", "promotion_b"]
+        );
+        assert_eq!(report.rows[0].click_through_rate, Some(15.0));
+        assert_eq!(report.rows[0].impression, Some(500));
+        assert_eq!(report.rows[0].media_played_100_percent_rate, Some(60.0));
+        // 分母が 0 の場合は計算しない
+        assert_eq!(report.rows[1].click_through_rate, None);
+
+        list_mock.assert_async().await;
+        for mock in unit_mocks {
+            mock.assert_async().await;
+        }
+    }
 }
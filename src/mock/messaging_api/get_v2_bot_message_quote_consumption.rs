@@ -1,111 +1,3 @@
-use derive_builder::Builder;
-use mockito::{Mock, Server};
-use serde::{Deserialize, Serialize};
-use serde_json::json;
+//! 旧名。URL は `/quota` なので [`get_v2_bot_message_quota_consumption`](super::get_v2_bot_message_quota_consumption) を使う。
 
-#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
-#[builder(setter(into))]
-#[builder(default)]
-#[builder(field(public))]
-pub struct MockParams {
-    pub channel_access_token: String,
-    pub status_code: usize,
-    pub total_usage: i64,
-    pub error_message: Option<String>,
-}
-
-pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
-    let mut builder = builder.unwrap_or_default();
-    if builder.channel_access_token.is_none() {
-        builder.channel_access_token("test_channel_access_token".to_string());
-    }
-    if builder.status_code.is_none() {
-        builder.status_code(200usize);
-    }
-    if builder.total_usage.is_none() {
-        builder.total_usage(1500i64);
-    }
-    if builder.error_message.is_none() {
-        builder.error_message("error occurred".to_string());
-    }
-    let params = builder.build().unwrap();
-
-    let body_json = if params.status_code == 200 {
-        json!({
-            "totalUsage": params.total_usage,
-        })
-    } else {
-        json!({
-            "message": params.error_message
-        })
-    };
-
-    server
-        .mock("GET", "/v2/bot/message/quota/consumption")
-        .match_header(
-            "authorization",
-            format!("Bearer {}", params.channel_access_token).as_str(),
-        )
-        .with_status(params.status_code)
-        .with_header("content-type", "application/json")
-        .with_body(body_json.to_string())
-        .create_async()
-        .await
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{
-        error::Error, messaging_api::get_v2_bot_message_quote_consumption, option::LineOptions,
-    };
-
-    use super::*;
-
-    // cargo test --all-features test_make_mock_get_v2_bot_message_quote_consumption_success -- --nocapture --test-threads=1
-    #[tokio::test]
-    async fn test_make_mock_get_v2_bot_message_quote_consumption_success() {
-        let mut server = Server::new_async().await;
-        let mut builder = MockParamsBuilder::default();
-        builder.total_usage(2500i64);
-        let mock = make_mock(&mut server, Some(builder)).await;
-
-        let res = get_v2_bot_message_quote_consumption::execute(
-            "test_channel_access_token",
-            &LineOptions::builder().with_prefix_url(server.url()).build(),
-        )
-        .await
-        .unwrap();
-
-        assert_eq!(res.0.total_usage, 2500);
-
-        mock.assert_async().await;
-    }
-
-    // cargo test --all-features test_make_mock_get_v2_bot_message_quote_consumption_failure -- --nocapture --test-threads=1
-    #[tokio::test]
-    async fn test_make_mock_get_v2_bot_message_quote_consumption_failure() {
-        let mut server = Server::new_async().await;
-        let mut builder = MockParamsBuilder::default();
-        builder.status_code(403usize);
-        let mock = make_mock(&mut server, Some(builder)).await;
-
-        let res = get_v2_bot_message_quote_consumption::execute(
-            "test_channel_access_token",
-            &LineOptions::builder().with_prefix_url(server.url()).build(),
-        )
-        .await;
-
-        match res {
-            Err(e) => match *e {
-                Error::Line(response, status_code, _header) => {
-                    assert_eq!(status_code, 403);
-                    assert_eq!(response.message, "error occurred");
-                }
-                _ => panic!("Unexpected error"),
-            },
-            _ => panic!("Unexpected response"),
-        }
-
-        mock.assert_async().await;
-    }
-}
+pub use super::get_v2_bot_message_quota_consumption::*;
@@ -0,0 +1,168 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub room_id: String,
+    pub status_code: usize,
+    pub count: u64,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.room_id.is_none() {
+        builder.room_id("R123456789".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.count.is_none() {
+        builder.count(3u64);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        json!({
+            "count": params.count
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock(
+            "GET",
+            format!("/v2/bot/room/{}/members/count", params.room_id).as_str(),
+        )
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, messaging_api::get_v2_bot_room_members_count, option::LineOptions};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_room_members_count_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_room_members_count_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.count(42u64);
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_room_members_count::execute(
+            "R123456789",
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.count, 42);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_room_members_count_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_room_members_count_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(404usize);
+        builder.error_message("Not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_room_members_count::execute(
+            "R123456789",
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 404);
+                    assert_eq!(response.message, "Not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // ChatSource は source.type に応じて group / room のエンドポイントを呼び分ける。
+    // cargo test --all-features test_make_mock_get_v2_bot_room_members_count_chat_source -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_room_members_count_chat_source() {
+        use crate::messaging_api::chat_source::ChatSource;
+
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.count(5u64);
+        let room_mock = make_mock(&mut server, Some(builder)).await;
+        let mut builder =
+            crate::mock::messaging_api::get_v2_bot_group_members_count::MockParamsBuilder::default(
+            );
+        builder.count(7u64);
+        let group_mock = crate::mock::messaging_api::get_v2_bot_group_members_count::make_mock(
+            &mut server,
+            Some(builder),
+        )
+        .await;
+        let options = LineOptions::builder().with_prefix_url(server.url()).build();
+
+        let source: ChatSource =
+            serde_json::from_value(json!({"type": "room", "roomId": "R123456789"})).unwrap();
+        let (count, _header) = source
+            .execute_members_count("test_channel_access_token", &options)
+            .await
+            .unwrap();
+        assert_eq!(count, 5);
+
+        let source: ChatSource =
+            serde_json::from_value(json!({"type": "group", "groupId": "C123456789"})).unwrap();
+        let (count, _header) = source
+            .execute_members_count("test_channel_access_token", &options)
+            .await
+            .unwrap();
+        assert_eq!(count, 7);
+
+        // 1:1 トークにはメンバー数 API が無いのでリクエストせずにエラーになる
+        let source: ChatSource =
+            serde_json::from_value(json!({"type": "user", "userId": "U123456789"})).unwrap();
+        let res = source
+            .execute_members_count("test_channel_access_token", &options)
+            .await;
+        assert!(matches!(*res.unwrap_err(), Error::Invalid(_)));
+
+        room_mock.assert_async().await;
+        group_mock.assert_async().await;
+    }
+}
@@ -0,0 +1,169 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub room_id: String,
+    pub start: Option<String>,
+    pub status_code: usize,
+    pub member_ids: Vec<String>,
+    pub next: Option<String>,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.room_id.is_none() {
+        builder.room_id("R123456789".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.member_ids.is_none() {
+        builder.member_ids(vec!["U111".to_string(), "U222".to_string()]);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        let mut response = json!({
+            "memberIds": params.member_ids,
+        });
+        if let Some(next) = params.next {
+            response["next"] = json!(next);
+        }
+        response
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut mock_builder = server
+        .mock(
+            "GET",
+            format!("/v2/bot/room/{}/members/ids", params.room_id).as_str(),
+        )
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        );
+    if let Some(start) = params.start {
+        mock_builder = mock_builder.match_query(Matcher::UrlEncoded("start".to_string(), start));
+    }
+
+    mock_builder
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, messaging_api::get_v2_bot_room_members_ids, option::LineOptions};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_get_v2_bot_room_members_ids_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_room_members_ids_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.start(Some("start_token".to_string()));
+        builder.next(Some("next_token".to_string()));
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_room_members_ids::QueryParams::new("start_token");
+        let res = get_v2_bot_room_members_ids::execute(
+            "R123456789",
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.member_ids, vec!["U111", "U222"]);
+        assert_eq!(res.0.next, Some("next_token".to_string()));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_room_members_ids_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_room_members_ids_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(403usize);
+        builder.error_message("Access to this API is not available".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let query_params = get_v2_bot_room_members_ids::QueryParams::new("");
+        let res = get_v2_bot_room_members_ids::execute(
+            "R123456789",
+            &query_params,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 403);
+                    assert_eq!(response.message, "Access to this API is not available");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_get_v2_bot_room_members_ids_stream -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_get_v2_bot_room_members_ids_stream() {
+        let mut server = Server::new_async().await;
+
+        let page1 = server
+            .mock("GET", "/v2/bot/room/R123456789/members/ids")
+            .match_query(Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"memberIds": ["U1", "U2"], "next": "page2_token"}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        let mut builder = MockParamsBuilder::default();
+        builder.start(Some("page2_token".to_string()));
+        builder.member_ids(vec!["U3".to_string()]);
+        let page2 = make_mock(&mut server, Some(builder)).await;
+
+        let res = get_v2_bot_room_members_ids::execute_stream(
+            "R123456789",
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+            100,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res, vec!["U1", "U2", "U3"]);
+        page1.assert_async().await;
+        page2.assert_async().await;
+    }
+}
@@ -0,0 +1,160 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub description: String,
+    pub request_id: String,
+    pub click_url: Option<String>,
+    pub audience_group_id: i64,
+    pub created: i64,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.description.is_none() {
+        builder.description("test click audience".to_string());
+    }
+    if builder.request_id.is_none() {
+        builder.request_id("bb9744f9-47fa-4a29-941e-1234567890ab".to_string());
+    }
+    if builder.audience_group_id.is_none() {
+        builder.audience_group_id(1234567890123i64);
+    }
+    if builder.created.is_none() {
+        builder.created(1613698278i64);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(202usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 202 {
+        json!({
+            "audienceGroupId": params.audience_group_id,
+            "createRoute": "MESSAGING_API",
+            "type": "CLICK",
+            "description": params.description,
+            "created": params.created,
+            "permission": "READ_WRITE",
+            "expireTimestamp": params.created + 15552000,
+            "isIfaAudience": false,
+            "requestId": params.request_id,
+            "clickUrl": params.click_url
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut expected_body = json!({
+        "description": params.description,
+        "requestId": params.request_id
+    });
+    if let Some(click_url) = &params.click_url {
+        expected_body["clickUrl"] = json!(click_url);
+    }
+
+    server
+        .mock("POST", "/v2/bot/audienceGroup/click")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_body(Matcher::PartialJson(expected_body))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{audience_group::AudienceGroupType, post_v2_bot_audience_group_click},
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_post_v2_bot_audience_group_click_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_audience_group_click_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.click_url(Some("https://example.com/".to_string()));
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let body = post_v2_bot_audience_group_click::RequestBody::new(
+            "test click audience",
+            "bb9744f9-47fa-4a29-941e-1234567890ab",
+            Some("https://example.com/"),
+        )
+        .unwrap();
+        let res = post_v2_bot_audience_group_click::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.audience_group_type, AudienceGroupType::Click);
+        assert_eq!(res.0.click_url, Some("https://example.com/".to_string()));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_post_v2_bot_audience_group_click_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_audience_group_click_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        builder.error_message("requestId not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let body = post_v2_bot_audience_group_click::RequestBody::new(
+            "test click audience",
+            "bb9744f9-47fa-4a29-941e-1234567890ab",
+            None,
+        )
+        .unwrap();
+        let res = post_v2_bot_audience_group_click::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "requestId not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
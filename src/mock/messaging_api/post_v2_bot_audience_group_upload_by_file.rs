@@ -0,0 +1,164 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub description: String,
+    // multipart の file パートに含まれていることを確認する ID
+    pub audiences: Vec<String>,
+    pub audience_group_id: i64,
+    pub created: i64,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+/// データ用ホスト向けのモック。`LineOptionsBuilder::with_data_prefix_url` に `server.url()` を渡して使う。
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.description.is_none() {
+        builder.description("test audience by file".to_string());
+    }
+    if builder.audiences.is_none() {
+        builder.audiences(vec!["U111".to_string(), "U222".to_string()]);
+    }
+    if builder.audience_group_id.is_none() {
+        builder.audience_group_id(1234567890123i64);
+    }
+    if builder.created.is_none() {
+        builder.created(1613698278i64);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(202usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 202 {
+        json!({
+            "audienceGroupId": params.audience_group_id,
+            "createRoute": "MESSAGING_API",
+            "type": "UPLOAD",
+            "description": params.description,
+            "created": params.created,
+            "permission": "READ_WRITE",
+            "expireTimestamp": params.created + 15552000,
+            "isIfaAudience": false
+        })
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut body_matchers = vec![
+        Matcher::Regex("name=\"description\"".to_string()),
+        Matcher::Regex(params.description.clone()),
+    ];
+    if !params.audiences.is_empty() {
+        body_matchers.push(Matcher::Regex(params.audiences.join("\n")));
+    }
+
+    server
+        .mock("POST", "/v2/bot/audienceGroup/upload/byFile")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_header(
+            "content-type",
+            Matcher::Regex("^multipart/form-data".to_string()),
+        )
+        .match_body(Matcher::AllOf(body_matchers))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error, messaging_api::post_v2_bot_audience_group_upload_by_file, option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_post_v2_bot_audience_group_upload_by_file_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_audience_group_upload_by_file_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let body = post_v2_bot_audience_group_upload_by_file::RequestBody::new(
+            "test audience by file",
+            vec!["U111".to_string(), "U222".to_string()],
+        )
+        .unwrap();
+        // 通常のホストには送られないこと
+        let res = post_v2_bot_audience_group_upload_by_file::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder()
+                .with_prefix_url("http://127.0.0.1:1")
+                .with_data_prefix_url(server.url())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0.audience_group_id, 1234567890123);
+        assert_eq!(res.0.description, "test audience by file");
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_post_v2_bot_audience_group_upload_by_file_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_audience_group_upload_by_file_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.audiences(vec!["U111".to_string()]);
+        builder.status_code(400usize);
+        builder.error_message("Invalid file".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let body = post_v2_bot_audience_group_upload_by_file::RequestBody::new(
+            "test audience by file",
+            vec!["U111".to_string()],
+        )
+        .unwrap();
+        let res = post_v2_bot_audience_group_upload_by_file::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder()
+                .with_data_prefix_url(server.url())
+                .build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "Invalid file");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,122 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub chat_id: String,
+    pub loading_seconds: Option<u8>,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.chat_id.is_none() {
+        builder.chat_id("U123456789".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(202usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code < 300 {
+        json!({})
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut expected_body = json!({
+        "chatId": params.chat_id
+    });
+    if let Some(loading_seconds) = params.loading_seconds {
+        expected_body["loadingSeconds"] = json!(loading_seconds);
+    }
+
+    server
+        .mock("POST", "/v2/bot/chat/loading/start")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_body(mockito::Matcher::Json(expected_body))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, messaging_api::post_v2_bot_chat_loading_start, option::LineOptions};
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_post_v2_bot_chat_loading_start_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_chat_loading_start_success() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.loading_seconds(Some(30u8));
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let request_body =
+            post_v2_bot_chat_loading_start::RequestBody::new("U123456789", Some(30)).unwrap();
+        let res = post_v2_bot_chat_loading_start::execute(
+            request_body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0, json!({}));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_post_v2_bot_chat_loading_start_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_chat_loading_start_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let request_body =
+            post_v2_bot_chat_loading_start::RequestBody::new("U123456789", None).unwrap();
+        let res = post_v2_bot_chat_loading_start::execute(
+            request_body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "error occurred");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,119 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub user_id: String,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.user_id.is_none() {
+        builder.user_id("U123456789".to_string());
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 200 {
+        json!({})
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let expected_body = json!({
+        "chat": {"userId": params.user_id}
+    });
+
+    server
+        .mock("POST", "/v2/bot/message/markAsRead")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_body(mockito::Matcher::Json(expected_body))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error, messaging_api::post_v2_bot_message_mark_as_read, option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_post_v2_bot_message_mark_as_read_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_mark_as_read_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let request_body =
+            post_v2_bot_message_mark_as_read::RequestBody::new("U123456789").unwrap();
+        let res = post_v2_bot_message_mark_as_read::execute(
+            request_body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0, json!({}));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_post_v2_bot_message_mark_as_read_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_mark_as_read_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(403usize);
+        builder.error_message("Access to this API is not available".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let request_body =
+            post_v2_bot_message_mark_as_read::RequestBody::new("U123456789").unwrap();
+        let res = post_v2_bot_message_mark_as_read::execute(
+            request_body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 403);
+                    assert_eq!(response.message, "Access to this API is not available");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+}
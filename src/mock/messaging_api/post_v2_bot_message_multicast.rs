@@ -0,0 +1,255 @@
+use derive_builder::Builder;
+use mockito::{Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub to: Vec<String>,
+    pub messages: Vec<serde_json::Value>,
+    pub status_code: usize,
+    pub response_message: Option<String>,
+    pub error_message: Option<String>,
+    pub accepted_request_id: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.to.is_none() {
+        builder.to(vec!["U123456789".to_string(), "U987654321".to_string()]);
+    }
+    if builder.messages.is_none() {
+        builder.messages(vec![json!({"type": "text", "text": "Hello!"})]);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(200usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    // 409 はリトライキー受理済みの応答
+    let body_json = if params.status_code == 200 || params.status_code == 409 {
+        let mut response = json!({});
+        if let Some(msg) = params.response_message {
+            response["message"] = json!(msg);
+        }
+        response
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut mock_builder = server
+        .mock("POST", "/v2/bot/message/multicast")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_body(mockito::Matcher::Json(json!({
+            "to": params.to,
+            "messages": params.messages
+        })))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json");
+    if let Some(accepted_request_id) = params.accepted_request_id {
+        mock_builder = mock_builder.with_header("x-line-accepted-request-id", &accepted_request_id);
+    }
+    mock_builder
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{
+            outbox::{DrainReport, MemoryMessageStore, MessageStore, Outbox},
+            post_v2_bot_message_multicast,
+        },
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    fn make_request_body() -> post_v2_bot_message_multicast::RequestBody {
+        post_v2_bot_message_multicast::RequestBody::new(
+            vec!["U123456789".to_string(), "U987654321".to_string()],
+            vec![json!({"type": "text", "text": "Hello!"})],
+        )
+        .unwrap()
+    }
+
+    // cargo test --all-features test_make_mock_post_v2_bot_message_multicast_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_multicast_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let res = post_v2_bot_message_multicast::execute(
+            make_request_body(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(res.0.message, None);
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_post_v2_bot_message_multicast_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_multicast_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        builder.error_message("The request body has 1 error(s)".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let res = post_v2_bot_message_multicast::execute(
+            make_request_body(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+            None,
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "The request body has 1 error(s)");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // 宛先は 1〜500 件
+    #[test]
+    fn test_post_v2_bot_message_multicast_request_body_validation() {
+        let messages = vec![json!({"type": "text", "text": "Hello!"})];
+        assert!(post_v2_bot_message_multicast::RequestBody::new(vec![], messages.clone()).is_err());
+        let to: Vec<String> = (0..=post_v2_bot_message_multicast::MAX_TO)
+            .map(|i| format!("U{i}"))
+            .collect();
+        assert!(post_v2_bot_message_multicast::RequestBody::new(to, messages.clone()).is_err());
+        let err = post_v2_bot_message_multicast::RequestBody::new(vec!["U1".to_string()], vec![])
+            .unwrap_err();
+        match *err {
+            Error::Validation(validation_error) => {
+                assert_eq!(validation_error.field, "messages");
+                assert_eq!(
+                    validation_error.constraint,
+                    crate::error::Constraint::Length { min: 1, max: 5 }
+                );
+                assert_eq!(validation_error.actual, 0);
+            }
+            _ => panic!("Unexpected error"),
+        }
+    }
+
+    // 409(リトライキー受理済み)は送信済みとして扱い、送信待ちから消える
+    // cargo test --all-features test_outbox_drain_multicast_accepted -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_outbox_drain_multicast_accepted() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(409usize);
+        builder.accepted_request_id("accepted-request-id".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let outbox = Outbox::new(
+            MemoryMessageStore::new(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        );
+        outbox.enqueue_multicast(make_request_body()).unwrap();
+        let report = outbox.drain().await.unwrap();
+        assert_eq!(
+            report,
+            DrainReport {
+                sent: 1,
+                ..Default::default()
+            }
+        );
+        assert!(outbox.store().pending().unwrap().is_empty());
+
+        mock.assert_async().await;
+    }
+
+    // 一時的なエラーは同じリトライキーのまま残し、それ以外はデッドレターへ移す
+    // cargo test --all-features test_outbox_drain_multicast_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_outbox_drain_multicast_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(500usize);
+        // Outbox はリトライキーを必ず送るため try_count を 2 にする
+        let mock = make_mock(&mut server, Some(builder)).await.expect(2);
+
+        let outbox = Outbox::new(
+            MemoryMessageStore::new(),
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .with_max_attempts(2);
+        let entry = outbox.enqueue_multicast(make_request_body()).unwrap();
+        let report = outbox.drain().await.unwrap();
+        assert_eq!(report.retrying, 1);
+        let pending = outbox.store().pending().unwrap();
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].retry_key, entry.retry_key);
+        assert!(pending[0].last_error.is_some());
+        mock.assert_async().await;
+        mock.remove_async().await;
+
+        // 2 回目の失敗で max_attempts に達する
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(500usize);
+        let mock = make_mock(&mut server, Some(builder)).await.expect(2);
+        let report = outbox.drain().await.unwrap();
+        assert_eq!(report.dead_lettered, 1);
+        assert!(outbox.store().pending().unwrap().is_empty());
+        mock.assert_async().await;
+        mock.remove_async().await;
+
+        // 4xx は 1 回目でデッドレターへ
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        builder.error_message("The request body has 1 error(s)".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+        let other = outbox.enqueue_multicast(make_request_body()).unwrap();
+        let report = outbox.drain().await.unwrap();
+        assert_eq!(report.dead_lettered, 1);
+        mock.assert_async().await;
+
+        let dead_letters = outbox.dead_letters().unwrap();
+        assert_eq!(dead_letters.len(), 2);
+        assert_eq!(dead_letters[1].entry.id, other.id);
+        assert_eq!(dead_letters[1].error["status_code"], 400);
+
+        // 再投入するとリトライキーはそのままで試行回数がリセットされる
+        let requeued = outbox.requeue_dead_letter(&entry.id).unwrap().unwrap();
+        assert_eq!(requeued.retry_key, entry.retry_key);
+        assert_eq!(requeued.attempts, 0);
+        assert_eq!(outbox.dead_letters().unwrap().len(), 1);
+        assert!(outbox.requeue_dead_letter(&entry.id).unwrap().is_none());
+    }
+}
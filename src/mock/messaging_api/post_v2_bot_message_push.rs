@@ -18,6 +18,8 @@ pub struct MockParams {
     pub sent_message_quote_tokens: Vec<Option<String>>,
     pub response_message: Option<String>,
     pub error_message: Option<String>,
+    pub request_id: Option<String>,
+    pub accepted_request_id: Option<String>,
 }
 
 pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
@@ -45,7 +47,8 @@ pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>)
     }
     let params = builder.build().unwrap();
 
-    let body_json = if params.status_code == 200 {
+    // 409 はリトライキー受理済みの応答として、sentMessages を含めて返す
+    let body_json = if params.status_code == 200 || params.status_code == 409 {
         let sent_messages: Vec<serde_json::Value> = params
             .sent_message_ids
             .iter()
@@ -94,7 +97,7 @@ pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>)
             })
         };
 
-    server
+    let mut mock_builder = server
         .mock("POST", "/v2/bot/message/push")
         .match_header(
             "authorization",
@@ -102,7 +105,14 @@ pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>)
         )
         .match_body(mockito::Matcher::Json(expected_body))
         .with_status(params.status_code)
-        .with_header("content-type", "application/json")
+        .with_header("content-type", "application/json");
+    if let Some(request_id) = params.request_id {
+        mock_builder = mock_builder.with_header("x-line-request-id", &request_id);
+    }
+    if let Some(accepted_request_id) = params.accepted_request_id {
+        mock_builder = mock_builder.with_header("x-line-accepted-request-id", &accepted_request_id);
+    }
+    mock_builder
         .with_body(body_json.to_string())
         .create_async()
         .await
@@ -128,7 +138,7 @@ mod tests {
         let request_body =
             post_v2_bot_message_push::RequestBody::new("U123456789", messages).unwrap();
 
-        let _res = post_v2_bot_message_push::execute(
+        let (res, _header) = post_v2_bot_message_push::execute(
             request_body,
             "test_channel_access_token",
             &LineOptions::builder().with_prefix_url(server.url()).build(),
@@ -137,10 +147,125 @@ mod tests {
         .await
         .unwrap();
 
-        // Note: ResponseBody fields are private, so we can only verify successful execution
-        // In a real implementation, you might want to make them public for testing
+        assert_eq!(res.sent_messages.len(), 1);
+        assert_eq!(res.sent_messages[0].id, "msg456");
+        assert_eq!(
+            res.sent_messages[0].quote_token,
+            Some("quote456".to_string())
+        );
+
+        mock.assert_async().await;
+    }
+
+    // 200 は PushOutcome::Sent、リトライキー付き 409 は AlreadyAccepted として区別できる。
+    // cargo test --all-features test_make_mock_post_v2_bot_message_push_outcome -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_push_outcome() {
+        let mut server = Server::new_async().await;
+        let options = LineOptions::builder()
+            .with_prefix_url(server.url())
+            .with_try_count(2)
+            .build();
+
+        // --- 新規送信: Sent ---
+        let mut builder = MockParamsBuilder::default();
+        builder.request_id(Some("req-1".to_string()));
+        let mock = make_mock(&mut server, Some(builder)).await;
+        let request_body = post_v2_bot_message_push::RequestBody::new(
+            "U123456789",
+            vec![json!({"type": "text", "text": "Hello!"})],
+        )
+        .unwrap();
+        let (res, outcome, header) = post_v2_bot_message_push::execute_with_outcome(
+            request_body.clone(),
+            "test_channel_access_token",
+            &options,
+            Some("retry-key-1".to_string()),
+        )
+        .await
+        .unwrap();
+        mock.assert_async().await;
+        mock.remove_async().await;
+        assert_eq!(outcome, post_v2_bot_message_push::PushOutcome::Sent);
+        assert_eq!(header.request_id, "req-1");
+        assert_eq!(res.message_ids(), vec!["msg123"]);
+        assert_eq!(res.message(), None);
 
+        // --- 同じリトライキーでの再送: AlreadyAccepted ---
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(409usize);
+        builder.response_message(Some("The retry key is already accepted".to_string()));
+        builder.accepted_request_id(Some("req-1".to_string()));
+        let mock = make_mock(&mut server, Some(builder)).await;
+        let (res, outcome, _header) = post_v2_bot_message_push::execute_with_outcome(
+            request_body,
+            "test_channel_access_token",
+            &options,
+            Some("retry-key-1".to_string()),
+        )
+        .await
+        .unwrap();
         mock.assert_async().await;
+        assert_eq!(
+            outcome,
+            post_v2_bot_message_push::PushOutcome::AlreadyAccepted {
+                accepted_request_id: Some("req-1".to_string())
+            }
+        );
+        assert!(outcome.is_already_accepted());
+        assert_eq!(res.message(), Some("The retry key is already accepted"));
+        assert_eq!(res.sent_messages()[0].id, "msg123");
+    }
+
+    // 送信結果の quoteToken を使い、sender 付きの引用リプライを続けて送れることを確認する。
+    // cargo test --all-features test_make_mock_post_v2_bot_message_push_quote_reply -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_push_quote_reply() {
+        use crate::messaging_api::message::{Message, Sender};
+
+        let mut server = Server::new_async().await;
+        let first = make_mock(&mut server, None).await;
+        let options = LineOptions::builder().with_prefix_url(server.url()).build();
+
+        let request_body = post_v2_bot_message_push::RequestBody::new(
+            "U123456789",
+            vec![json!({"type": "text", "text": "Hello!"})],
+        )
+        .unwrap();
+        let (res, _header) = post_v2_bot_message_push::execute(
+            request_body,
+            "test_channel_access_token",
+            &options,
+            None,
+        )
+        .await
+        .unwrap();
+        first.assert_async().await;
+        let quote_token = res.sent_messages[0].quote_token.clone().unwrap();
+
+        let reply = Message::text("Reply")
+            .with_sender(Sender::new(Some("Support"), None))
+            .with_quote_token(&quote_token);
+        let mut builder = MockParamsBuilder::default();
+        builder.messages(vec![json!({
+            "type": "text",
+            "text": "Reply",
+            "quoteToken": "token123",
+            "sender": {"name": "Support"}
+        })]);
+        let second = make_mock(&mut server, Some(builder)).await;
+
+        let request_body =
+            post_v2_bot_message_push::RequestBody::new("U123456789", vec![reply.into()]).unwrap();
+        let _res = post_v2_bot_message_push::execute(
+            request_body,
+            "test_channel_access_token",
+            &options,
+            None,
+        )
+        .await
+        .unwrap();
+        second.assert_async().await;
     }
 
     // cargo test --all-features test_make_mock_post_v2_bot_message_push_failure -- --nocapture --test-threads=1
@@ -319,6 +444,7 @@ mod tests {
         let sc = res_count.clone();
 
         // try_count=3, retry_duration=0 (待機なし) で試行ごとに発火することを確認
+        // (リトライキーの無い POST は 5xx でリトライしないため、リトライキーを付ける)
         let options = LineOptions::builder()
             .with_prefix_url(server.url())
             .with_try_count(3)
@@ -339,7 +465,7 @@ mod tests {
             request_body,
             "test_channel_access_token",
             &options,
-            None,
+            Some("retry-key-1".to_string()),
         )
         .await;
 
@@ -615,4 +741,83 @@ mod tests {
         // 非JSONなので execute は OtherText を返す
         assert!(matches!(*res.unwrap_err(), Error::OtherText(_, _, _)));
     }
+
+    // with_auto_retry_key: retry_key を渡さなくても UUIDv4 を生成し、リトライでも同じキーを使い、
+    // 使ったキーを LineResponseHeader で返す。リトライしない設定では生成しない。
+    // cargo test --all-features test_make_mock_post_v2_bot_message_push_auto_retry_key -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_post_v2_bot_message_push_auto_retry_key() {
+        use std::sync::{Arc, Mutex};
+
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(500usize);
+        let failure = make_mock(&mut server, Some(builder)).await;
+        let success = make_mock(&mut server, None).await;
+
+        let captured = Arc::new(Mutex::new(Vec::<String>::new()));
+        let c = captured.clone();
+        let options = LineOptions::builder()
+            .with_prefix_url(server.url())
+            .with_try_count(2)
+            .with_auto_retry_key(true)
+            .with_on_request(move |log| {
+                if let Some(value) = log.headers().and_then(|h| h.get("x-line-retry-key")) {
+                    c.lock().unwrap().push(value.to_str().unwrap().to_owned());
+                }
+            })
+            .build();
+        let request_body = post_v2_bot_message_push::RequestBody::new(
+            "U123456789",
+            vec![json!({"type": "text", "text": "Hello!"})],
+        )
+        .unwrap();
+        let (_res, header) = post_v2_bot_message_push::execute(
+            request_body.clone(),
+            "test_channel_access_token",
+            &options,
+            None,
+        )
+        .await
+        .unwrap();
+        failure.assert_async().await;
+        success.assert_async().await;
+
+        let retry_key = header.retry_key.unwrap();
+        assert_eq!(
+            uuid::Uuid::parse_str(&retry_key).unwrap().get_version_num(),
+            4
+        );
+        assert_eq!(*captured.lock().unwrap(), vec![retry_key.clone(); 2]);
+
+        // 渡したキーはそのまま使われる
+        let mock = make_mock(&mut server, None).await;
+        let (_res, header) = post_v2_bot_message_push::execute(
+            request_body.clone(),
+            "test_channel_access_token",
+            &options,
+            Some("retry-key-1".to_string()),
+        )
+        .await
+        .unwrap();
+        mock.assert_async().await;
+        assert_eq!(header.retry_key.as_deref(), Some("retry-key-1"));
+
+        // try_count=1 ならリトライしないので生成しない
+        let mock = make_mock(&mut server, None).await;
+        let options = LineOptions::builder()
+            .with_prefix_url(server.url())
+            .with_auto_retry_key(true)
+            .build();
+        let (_res, header) = post_v2_bot_message_push::execute(
+            request_body,
+            "test_channel_access_token",
+            &options,
+            None,
+        )
+        .await
+        .unwrap();
+        mock.assert_async().await;
+        assert_eq!(header.retry_key, None);
+    }
 }
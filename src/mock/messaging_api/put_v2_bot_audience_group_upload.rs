@@ -0,0 +1,130 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub audience_group_id: i64,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.audience_group_id.is_none() {
+        builder.audience_group_id(1234567890123i64);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(202usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 202 {
+        json!({})
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    server
+        .mock("PUT", "/v2/bot/audienceGroup/upload")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_body(Matcher::PartialJson(json!({
+            "audienceGroupId": params.audience_group_id
+        })))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error,
+        messaging_api::{audience_group::Audience, put_v2_bot_audience_group_upload},
+        option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let body = put_v2_bot_audience_group_upload::RequestBody::new(
+            1234567890123,
+            vec![Audience::new("U333")],
+        )
+        .unwrap();
+        let res = put_v2_bot_audience_group_upload::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0, json!({}));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(404usize);
+        builder.error_message("Audience group not found".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let body = put_v2_bot_audience_group_upload::RequestBody::new(
+            1234567890123,
+            vec![Audience::new("U333")],
+        )
+        .unwrap();
+        let res = put_v2_bot_audience_group_upload::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder().with_prefix_url(server.url()).build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 404);
+                    assert_eq!(response.message, "Audience group not found");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn test_put_v2_bot_audience_group_upload_empty() {
+        assert!(put_v2_bot_audience_group_upload::RequestBody::new(1, vec![]).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use derive_builder::Builder;
+use mockito::{Matcher, Mock, Server};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Builder, Default)]
+#[builder(setter(into))]
+#[builder(default)]
+#[builder(field(public))]
+pub struct MockParams {
+    pub channel_access_token: String,
+    pub audience_group_id: i64,
+    // multipart の file パートに含まれていることを確認する ID
+    pub audiences: Vec<String>,
+    pub status_code: usize,
+    pub error_message: Option<String>,
+}
+
+/// データ用ホスト向けのモック。`LineOptionsBuilder::with_data_prefix_url` に `server.url()` を渡して使う。
+pub async fn make_mock(server: &mut Server, builder: Option<MockParamsBuilder>) -> Mock {
+    let mut builder = builder.unwrap_or_default();
+    if builder.channel_access_token.is_none() {
+        builder.channel_access_token("test_channel_access_token".to_string());
+    }
+    if builder.audience_group_id.is_none() {
+        builder.audience_group_id(1234567890123i64);
+    }
+    if builder.audiences.is_none() {
+        builder.audiences(vec!["U333".to_string()]);
+    }
+    if builder.status_code.is_none() {
+        builder.status_code(202usize);
+    }
+    if builder.error_message.is_none() {
+        builder.error_message("error occurred".to_string());
+    }
+    let params = builder.build().unwrap();
+
+    let body_json = if params.status_code == 202 {
+        json!({})
+    } else {
+        json!({
+            "message": params.error_message
+        })
+    };
+
+    let mut body_matchers = vec![
+        Matcher::Regex("name=\"audienceGroupId\"".to_string()),
+        Matcher::Regex(params.audience_group_id.to_string()),
+    ];
+    if !params.audiences.is_empty() {
+        body_matchers.push(Matcher::Regex(params.audiences.join("\n")));
+    }
+
+    server
+        .mock("PUT", "/v2/bot/audienceGroup/upload/byFile")
+        .match_header(
+            "authorization",
+            format!("Bearer {}", params.channel_access_token).as_str(),
+        )
+        .match_header(
+            "content-type",
+            Matcher::Regex("^multipart/form-data".to_string()),
+        )
+        .match_body(Matcher::AllOf(body_matchers))
+        .with_status(params.status_code)
+        .with_header("content-type", "application/json")
+        .with_body(body_json.to_string())
+        .create_async()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error, messaging_api::put_v2_bot_audience_group_upload_by_file, option::LineOptions,
+    };
+
+    use super::*;
+
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_by_file_success -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_by_file_success() {
+        let mut server = Server::new_async().await;
+        let mock = make_mock(&mut server, None).await;
+
+        let body = put_v2_bot_audience_group_upload_by_file::RequestBody::new(
+            1234567890123,
+            vec!["U333".to_string()],
+        )
+        .unwrap();
+        let res = put_v2_bot_audience_group_upload_by_file::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder()
+                .with_data_prefix_url(server.url())
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.0, json!({}));
+
+        mock.assert_async().await;
+    }
+
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_by_file_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_by_file_failure() {
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.status_code(400usize);
+        builder.error_message("Invalid file".to_string());
+        let mock = make_mock(&mut server, Some(builder)).await;
+
+        let body = put_v2_bot_audience_group_upload_by_file::RequestBody::new(
+            1234567890123,
+            vec!["U333".to_string()],
+        )
+        .unwrap();
+        let res = put_v2_bot_audience_group_upload_by_file::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder()
+                .with_data_prefix_url(server.url())
+                .build(),
+        )
+        .await;
+
+        match res {
+            Err(e) => match *e {
+                Error::Line(response, status_code, _header) => {
+                    assert_eq!(status_code, 400);
+                    assert_eq!(response.message, "Invalid file");
+                }
+                _ => panic!("Unexpected error"),
+            },
+            _ => panic!("Unexpected response"),
+        }
+
+        mock.assert_async().await;
+    }
+
+    // multipart で複製できないリクエストでも、PUT は冪等なので 500 の後にリトライすることを確認する。
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_by_file_retry -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_by_file_retry() {
+        let mut server = Server::new_async().await;
+        let server_error = server
+            .mock("PUT", "/v2/bot/audienceGroup/upload/byFile")
+            .with_status(500)
+            .with_header("content-type", "application/json")
+            .with_body(json!({"message": "error occurred"}).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        let mock = make_mock(&mut server, None).await;
+
+        let body = put_v2_bot_audience_group_upload_by_file::RequestBody::new(
+            1234567890123,
+            vec!["U333".to_string()],
+        )
+        .unwrap();
+        put_v2_bot_audience_group_upload_by_file::execute(
+            body,
+            "test_channel_access_token",
+            &LineOptions::builder()
+                .with_data_prefix_url(server.url())
+                .with_try_count(2)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+        server_error.assert_async().await;
+        mock.assert_async().await;
+    }
+
+    // 5 件を 2 件ずつに分割し、作成 1 回 + 追加 2 回になることを確認する。
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_by_file_chunked -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_by_file_chunked() {
+        use crate::messaging_api::audience_group_file_upload::FileUpload;
+        use crate::mock::messaging_api::post_v2_bot_audience_group_upload_by_file as post_mock;
+
+        let mut server = Server::new_async().await;
+        let mut builder = post_mock::MockParamsBuilder::default();
+        builder.description("large audience".to_string());
+        builder.audiences(vec!["U1".to_string(), "U2".to_string()]);
+        let create_mock = post_mock::make_mock(&mut server, Some(builder)).await;
+        let mut builder = MockParamsBuilder::default();
+        builder.audiences(vec!["U3".to_string(), "U4".to_string()]);
+        let add_mock1 = make_mock(&mut server, Some(builder)).await;
+        let mut builder = MockParamsBuilder::default();
+        builder.audiences(vec!["U5".to_string()]);
+        let add_mock2 = make_mock(&mut server, Some(builder)).await;
+
+        // 空行は読み飛ばされる
+        let input: &[u8] = b"U1\nU2\n\nU3\nU4\nU5\n";
+        let report = FileUpload::new("large audience")
+            .unwrap()
+            .with_chunk_size(2)
+            .unwrap()
+            .execute_from_reader(
+                input,
+                "test_channel_access_token",
+                &LineOptions::builder()
+                    .with_data_prefix_url(server.url())
+                    .build(),
+            )
+            .await;
+
+        assert!(report.is_success());
+        assert_eq!(report.audience_group_id, Some(1234567890123));
+        assert!(report.created.is_some());
+        assert_eq!(
+            report
+                .chunks
+                .iter()
+                .map(|it| it.audience_count)
+                .collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(report.uploaded_count(), 5);
+
+        create_mock.assert_async().await;
+        add_mock1.assert_async().await;
+        add_mock2.assert_async().await;
+    }
+
+    // 既存オーディエンスへの追加で 2 チャンク目が失敗したら打ち切ることを確認する。
+    // cargo test --all-features test_make_mock_put_v2_bot_audience_group_upload_by_file_chunked_failure -- --nocapture --test-threads=1
+    #[tokio::test]
+    async fn test_make_mock_put_v2_bot_audience_group_upload_by_file_chunked_failure() {
+        use crate::messaging_api::audience_group_file_upload::FileUpload;
+
+        let mut server = Server::new_async().await;
+        let mut builder = MockParamsBuilder::default();
+        builder.audiences(vec!["U1".to_string(), "U2".to_string()]);
+        let add_mock1 = make_mock(&mut server, Some(builder)).await;
+        let mut builder = MockParamsBuilder::default();
+        builder.audiences(vec!["U3".to_string(), "U4".to_string()]);
+        builder.status_code(400usize);
+        builder.error_message("Invalid file".to_string());
+        let add_mock2 = make_mock(&mut server, Some(builder)).await;
+
+        let report = FileUpload::to_existing(1234567890123)
+            .with_chunk_size(2)
+            .unwrap()
+            .execute_from_iter(
+                ["U1", "U2", "U3", "U4", "U5"],
+                "test_channel_access_token",
+                &LineOptions::builder()
+                    .with_data_prefix_url(server.url())
+                    .build(),
+            )
+            .await;
+
+        assert!(!report.is_success());
+        assert!(report.created.is_none());
+        assert_eq!(report.chunks.len(), 2);
+        assert_eq!(report.uploaded_count(), 2);
+        match report.error() {
+            Some(Error::Line(response, status_code, _header)) => {
+                assert_eq!(*status_code, 400);
+                assert_eq!(response.message, "Invalid file");
+            }
+            _ => panic!("Unexpected report"),
+        }
+
+        add_mock1.assert_async().await;
+        add_mock2.assert_async().await;
+    }
+}
@@ -6,6 +6,7 @@
 //! 再エクスポートされており、`lineapi::LineOptions` のように従来どおりのパスで参照できる。
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 
@@ -15,10 +16,21 @@ use reqwest::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    rate_limit::{RateLimit, RateLimitGroup, RateLimiter},
+    retry::{BackoffPolicy, RetryPolicy},
+};
+
 /// API のベース URL のデフォルト。
 const PREFIX_URL: &str = "https://api.line.me";
 /// ベース URL を上書きする環境変数名。
 const ENV_KEY: &str = "LINE_API_PREFIX_URL";
+/// ファイルアップロード等で使うデータ用 API のベース URL のデフォルト。
+const DATA_PREFIX_URL: &str = "https://api-data.line.me";
+/// データ用ベース URL を上書きする環境変数名。
+const DATA_ENV_KEY: &str = "LINE_API_DATA_PREFIX_URL";
+/// `Retry-After` に従って待つ時間の上限のデフォルト。
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
 
 /// 秘匿情報をマスクする際の置換文字列。
 const REDACTED: &str = "***";
@@ -275,8 +287,8 @@ impl<'a> LineResponseLog<'a> {
 
     /// レスポンスヘッダー。
     ///
-    /// `on_response` コールバック設定時のみ複製・保持される(未設定時は空)。この
-    /// アクセサは `on_response` 経路からのみ到達する。
+    /// `Retry-After` やリトライ方針の判定に使うため、コールバックの有無にかかわらず常に
+    /// 複製・保持される。このアクセサは `on_response` 経路からのみ到達する。
     pub fn headers(&self) -> &'a HeaderMap {
         self.headers
     }
@@ -403,7 +415,7 @@ fn redact_body(value: &serde_json::Value, keys: &[String]) -> serde_json::Value
 /// 正しく解釈される。再エンコードは**秘匿キーを含まないペアにも及ぶ**ため、マスクの有無に関わらず
 /// 表現が正規化され得る(空白の `+`↔`%20`、`=` 無しの裸キー `foo` → `foo=` など)。生値の忠実な
 /// 再現が必要なら呼び出し側は [`LineRequestLog::query`] を使うこと。
-fn redact_query(query: &str, keys: &[String]) -> String {
+pub(crate) fn redact_query(query: &str, keys: &[String]) -> String {
     let pairs = url::form_urlencoded::parse(query.as_bytes()).map(|(key, value)| {
         if keys.contains(&key.to_ascii_lowercase()) {
             (key.into_owned(), REDACTED.to_string())
@@ -471,9 +483,21 @@ pub type OnResponse = Arc<dyn Fn(&LineRequestLog, &LineResponseLog) + Send + Syn
 #[non_exhaustive]
 pub struct LineOptions {
     pub(crate) prefix_url: Option<String>,
+    /// データ用 API(`api-data.line.me`)のベース URL。
+    pub(crate) data_prefix_url: Option<String>,
     pub(crate) timeout_duration: Option<Duration>,
     pub(crate) try_count: Option<u8>,
     pub(crate) retry_duration: Option<Duration>,
+    /// `Retry-After` に従って待つ時間の上限。
+    pub(crate) max_retry_after: Option<Duration>,
+    /// クライアント側のレート制限(指定時のみ)。クローン間でバケットを共有する。
+    #[serde(skip)]
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    /// リトライ方針(未設定時は try_count / retry_duration から組み立てる)。
+    #[serde(skip)]
+    pub(crate) retry_policy: Option<Arc<dyn RetryPolicy>>,
+    /// リトライキーの自動生成。
+    pub(crate) auto_retry_key: Option<bool>,
     /// リクエスト送信直前に呼ばれるコールバック(指定時のみ)。
     #[serde(skip)]
     pub(crate) on_request: Option<OnRequest>,
@@ -489,9 +513,14 @@ impl std::fmt::Debug for LineOptions {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LineOptions")
             .field("prefix_url", &self.prefix_url)
+            .field("data_prefix_url", &self.data_prefix_url)
             .field("timeout_duration", &self.timeout_duration)
             .field("try_count", &self.try_count)
             .field("retry_duration", &self.retry_duration)
+            .field("max_retry_after", &self.max_retry_after)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("retry_policy", &self.retry_policy)
+            .field("auto_retry_key", &self.auto_retry_key)
             .field("on_request", &self.on_request.as_ref().map(|_| "Fn"))
             .field("on_response", &self.on_response.as_ref().map(|_| "Fn"))
             .field("redacted_body_keys", &self.redacted_body_keys)
@@ -528,6 +557,39 @@ impl LineOptions {
         self.retry_duration.unwrap_or(Duration::from_secs(0))
     }
 
+    /// `Retry-After` に従って待つ時間の上限(未設定時は 60 秒)。
+    pub fn get_max_retry_after(&self) -> Duration {
+        self.max_retry_after.unwrap_or(MAX_RETRY_AFTER)
+    }
+
+    /// [`LineOptionsBuilder::with_rate_limit`] で設定したレート制限。
+    pub fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// 実際に使用されるリトライ方針を返す。
+    ///
+    /// [`LineOptionsBuilder::with_retry_policy`] 未設定時は、`try_count` 回まで
+    /// `retry_duration` を基準に待つ [`BackoffPolicy::exponential`](従来の動作)。
+    pub fn get_retry_policy(&self) -> Arc<dyn RetryPolicy> {
+        self.retry_policy.clone().unwrap_or_else(|| {
+            Arc::new(BackoffPolicy::exponential(
+                self.get_try_count() as u32,
+                self.get_retry_duration(),
+            ))
+        })
+    }
+
+    /// リトライキーを自動生成するか(未設定時は `false`)。
+    pub fn get_auto_retry_key(&self) -> bool {
+        self.auto_retry_key.unwrap_or(false)
+    }
+
+    /// リトライする可能性があるか(リトライ方針を設定済み、または `try_count` が 2 以上)。
+    pub(crate) fn may_retry(&self) -> bool {
+        self.retry_policy.is_some() || self.get_try_count() > 1
+    }
+
     pub fn get_timeout_duration(&self) -> Duration {
         self.timeout_duration.unwrap_or(Duration::from_secs(0))
     }
@@ -540,6 +602,14 @@ impl LineOptions {
         self.resolve_prefix_url()
     }
 
+    /// 実際に使用されるデータ用 API のベース URL を返す。
+    ///
+    /// [`LineOptionsBuilder::with_data_prefix_url`] 未設定時は環境変数 `LINE_API_DATA_PREFIX_URL`、
+    /// それも無ければデフォルトの `https://api-data.line.me` を返す。
+    pub fn get_data_prefix_url(&self) -> String {
+        self.resolve_data_prefix_url()
+    }
+
     /// `body_redacted` でマスクされるボディキーの実効値を返す。
     ///
     /// [`LineOptionsBuilder::with_redacted_body_keys`] 未設定時は既定の
@@ -557,6 +627,13 @@ impl LineOptions {
             .clone()
             .unwrap_or_else(|| std::env::var(ENV_KEY).unwrap_or_else(|_| PREFIX_URL.to_string()))
     }
+
+    /// `data_prefix_url` の実効値を解決する(設定値 → 環境変数 → デフォルト)。
+    pub(crate) fn resolve_data_prefix_url(&self) -> String {
+        self.data_prefix_url.clone().unwrap_or_else(|| {
+            std::env::var(DATA_ENV_KEY).unwrap_or_else(|_| DATA_PREFIX_URL.to_string())
+        })
+    }
 }
 
 /// [`LineOptions`] を組み立てるビルダー。
@@ -572,9 +649,14 @@ impl LineOptions {
 #[derive(Default, Clone)]
 pub struct LineOptionsBuilder {
     prefix_url: Option<String>,
+    data_prefix_url: Option<String>,
     timeout_duration: Option<Duration>,
     try_count: Option<u8>,
     retry_duration: Option<Duration>,
+    max_retry_after: Option<Duration>,
+    rate_limits: Option<HashMap<RateLimitGroup, RateLimit>>,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    auto_retry_key: Option<bool>,
     on_request: Option<OnRequest>,
     on_response: Option<OnResponse>,
     redacted_body_keys: Option<Vec<String>>,
@@ -586,9 +668,14 @@ impl std::fmt::Debug for LineOptionsBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LineOptionsBuilder")
             .field("prefix_url", &self.prefix_url)
+            .field("data_prefix_url", &self.data_prefix_url)
             .field("timeout_duration", &self.timeout_duration)
             .field("try_count", &self.try_count)
             .field("retry_duration", &self.retry_duration)
+            .field("max_retry_after", &self.max_retry_after)
+            .field("rate_limits", &self.rate_limits)
+            .field("retry_policy", &self.retry_policy)
+            .field("auto_retry_key", &self.auto_retry_key)
             .field("on_request", &self.on_request.as_ref().map(|_| "Fn"))
             .field("on_response", &self.on_response.as_ref().map(|_| "Fn"))
             .field("redacted_body_keys", &self.redacted_body_keys)
@@ -606,9 +693,16 @@ impl LineOptionsBuilder {
     pub fn build(self) -> LineOptions {
         LineOptions {
             prefix_url: self.prefix_url,
+            data_prefix_url: self.data_prefix_url,
             timeout_duration: self.timeout_duration,
             try_count: self.try_count,
             retry_duration: self.retry_duration,
+            max_retry_after: self.max_retry_after,
+            rate_limiter: self
+                .rate_limits
+                .map(|limits| Arc::new(RateLimiter::new(limits))),
+            retry_policy: self.retry_policy,
+            auto_retry_key: self.auto_retry_key,
             on_request: self.on_request,
             on_response: self.on_response,
             redacted_body_keys: self.redacted_body_keys,
@@ -621,6 +715,12 @@ impl LineOptionsBuilder {
         self
     }
 
+    /// データ用 API(オーディエンスのファイルアップロード等)のベース URL を設定する。
+    pub fn with_data_prefix_url(mut self, data_prefix_url: impl Into<String>) -> Self {
+        self.data_prefix_url = Some(data_prefix_url.into());
+        self
+    }
+
     /// リクエストのタイムアウトを設定する。
     pub fn with_timeout_duration(mut self, timeout_duration: Duration) -> Self {
         self.timeout_duration = Some(timeout_duration);
@@ -642,6 +742,62 @@ impl LineOptionsBuilder {
         self
     }
 
+    /// リトライ方針を設定する。
+    ///
+    /// 設定すると `try_count` / `retry_duration` は使われず、各試行の失敗後に
+    /// [`RetryPolicy::next`] の判断で待つかやめるかが決まる。組み込みの方針は
+    /// [`BackoffPolicy`] を参照。
+    pub fn with_retry_policy(mut self, retry_policy: impl RetryPolicy + 'static) -> Self {
+        self.retry_policy = Some(Arc::new(retry_policy));
+        self
+    }
+
+    /// リトライキー(`X-Line-Retry-Key`)の自動生成を設定する。
+    ///
+    /// 有効にすると、リトライキーに対応したエンドポイント(push など)で `retry_key` に `None`
+    /// を渡したときに UUIDv4 を生成して付ける。リトライしない設定(`try_count` が 1 以下で
+    /// リトライ方針も未設定)の場合は生成しない。使ったキーは
+    /// [`LineResponseHeader::retry_key`](crate::LineResponseHeader::retry_key) で返る。
+    pub fn with_auto_retry_key(mut self, auto_retry_key: bool) -> Self {
+        self.auto_retry_key = Some(auto_retry_key);
+        self
+    }
+
+    /// `Retry-After` ヘッダーに従って待つ時間の上限を設定する。
+    ///
+    /// リトライ対象のエラーレスポンスに `Retry-After`(秒数または HTTP-date)があれば、
+    /// 指数バックオフの代わりにその時間(この上限で切り詰める)だけ待ってからリトライする。
+    /// `Duration::ZERO` を渡すと `Retry-After` を無視して従来どおりのバックオフになる。
+    pub fn with_max_retry_after(mut self, max_retry_after: Duration) -> Self {
+        self.max_retry_after = Some(max_retry_after);
+        self
+    }
+
+    /// グループごとのクライアント側レート制限を設定する(同じグループは上書き)。
+    ///
+    /// 設定したグループへのリクエストは、各試行の前にトークンバケットからトークンを取得し、
+    /// 足りなければ補充されるまで待つ。詳細は [`crate::rate_limit`] を参照。
+    pub fn with_rate_limit(mut self, group: RateLimitGroup, rate_limit: RateLimit) -> Self {
+        self.rate_limits
+            .get_or_insert_with(HashMap::new)
+            .insert(group, rate_limit);
+        self
+    }
+
+    /// すべてのグループに LINE が公開している上限([`RateLimit::line_default`])を設定する。
+    pub fn with_line_rate_limits(mut self) -> Self {
+        for group in [
+            RateLimitGroup::Push,
+            RateLimitGroup::Multicast,
+            RateLimitGroup::Broadcast,
+            RateLimitGroup::Narrowcast,
+            RateLimitGroup::Other,
+        ] {
+            self = self.with_rate_limit(group, RateLimit::line_default(group));
+        }
+        self
+    }
+
     /// `body_redacted` でマスクするボディキーを設定する。
     ///
     /// 未設定時は既定の [`REDACTED_BODY_KEYS`](`client_secret` / `access_token` /
@@ -822,6 +978,36 @@ mod tests {
         assert!(restored.on_response.is_none());
     }
 
+    // data_prefix_url は prefix_url とは独立に設定される
+    #[test]
+    fn test_data_prefix_url() {
+        let options = LineOptions::builder()
+            .with_prefix_url("https://example.com")
+            .with_data_prefix_url("https://data.example.com")
+            .build();
+        assert_eq!(options.get_prefix_url(), "https://example.com");
+        assert_eq!(options.get_data_prefix_url(), "https://data.example.com");
+    }
+
+    // broadcast と narrowcast は別のバケットで制限される
+    #[test]
+    fn test_with_line_rate_limits() {
+        let options = LineOptions::builder().with_line_rate_limits().build();
+        let limiter = options.get_rate_limiter().unwrap();
+        assert_eq!(
+            limiter.get_limit(RateLimitGroup::Broadcast),
+            Some(RateLimit::per_hour(60))
+        );
+        assert_eq!(
+            limiter.get_limit(RateLimitGroup::Narrowcast),
+            Some(RateLimit::per_hour(60))
+        );
+        assert_eq!(
+            limiter.get_limit(RateLimitGroup::Push),
+            Some(RateLimit::per_second(2000))
+        );
+    }
+
     // テスト用に CapturedRequest を組み立てるヘルパー(headers は空で十分)。
     fn make_captured(method: Method, path: &str, query: Option<&str>) -> CapturedRequest {
         CapturedRequest {
@@ -0,0 +1,240 @@
+//! クライアント側のレート制限(トークンバケット)。
+//!
+//! LINE のレート制限はエンドポイントごとに異なる
+//! (<https://developers.line.biz/ja/reference/messaging-api/#rate-limits>)。
+//! [`LineOptionsBuilder::with_rate_limit`](crate::LineOptionsBuilder::with_rate_limit) で
+//! [`RateLimitGroup`] ごとに [`RateLimit`] を設定すると、`execute_api` が各試行の前に
+//! トークンを取得し、上限を超えそうなときは送信前に待機する。429 を受けてからリトライで
+//! 回避するのではなく、大量送信のジョブが最初から制限内に収まるようにするためのもの。
+//!
+//! バケットは [`LineOptions`](crate::LineOptions) のクローン間で共有されるため、同じ
+//! `LineOptions` を複数タスクで使い回せばまとめて制限される。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+/// レート制限を共有するエンドポイントのまとまり。
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Display, Clone, Copy)]
+pub enum RateLimitGroup {
+    /// `/v2/bot/message/push`
+    #[serde(rename = "push")]
+    Push,
+    /// `/v2/bot/message/multicast`
+    #[serde(rename = "multicast")]
+    Multicast,
+    /// `/v2/bot/message/broadcast`(1 時間あたりの制限)
+    #[serde(rename = "broadcast")]
+    Broadcast,
+    /// `/v2/bot/message/narrowcast`(1 時間あたりの制限。broadcast とは別に数えられる)
+    #[serde(rename = "narrowcast")]
+    Narrowcast,
+    /// 上記以外のすべてのエンドポイント
+    #[serde(rename = "other")]
+    Other,
+}
+
+impl RateLimitGroup {
+    /// リクエストのパスからグループを判定する。
+    ///
+    /// ベース URL にパスが含まれていても判定できるよう、末尾で照合する。
+    pub fn from_path(path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+        if path.ends_with("/v2/bot/message/push") {
+            RateLimitGroup::Push
+        } else if path.ends_with("/v2/bot/message/multicast") {
+            RateLimitGroup::Multicast
+        } else if path.ends_with("/v2/bot/message/broadcast") {
+            RateLimitGroup::Broadcast
+        } else if path.ends_with("/v2/bot/message/narrowcast") {
+            RateLimitGroup::Narrowcast
+        } else {
+            RateLimitGroup::Other
+        }
+    }
+}
+
+/// `per` あたり `capacity` 回までのレート制限。
+///
+/// バケットは満タン(`capacity` 個)から始まり、`per / capacity` ごとに 1 個ずつ補充される。
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct RateLimit {
+    pub capacity: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(capacity: u32, per: Duration) -> Self {
+        Self { capacity, per }
+    }
+
+    pub fn per_second(capacity: u32) -> Self {
+        Self::new(capacity, Duration::from_secs(1))
+    }
+
+    pub fn per_hour(capacity: u32) -> Self {
+        Self::new(capacity, Duration::from_secs(60 * 60))
+    }
+
+    /// LINE が公開しているグループごとの上限。
+    pub fn line_default(group: RateLimitGroup) -> Self {
+        match group {
+            RateLimitGroup::Push => Self::per_second(2000),
+            RateLimitGroup::Multicast => Self::per_second(200),
+            RateLimitGroup::Broadcast => Self::per_hour(60),
+            RateLimitGroup::Narrowcast => Self::per_hour(60),
+            RateLimitGroup::Other => Self::per_second(2000),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    // 予約済みの分だけ負になり得る(先着順に待機時間を割り当てるため)
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.capacity as f64,
+            updated_at: Instant::now(),
+        }
+    }
+
+    // トークンを 1 個予約し、使えるようになるまでの待ち時間を返す
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let capacity = self.limit.capacity.max(1) as f64;
+        let refill_per_sec = capacity / self.limit.per.as_secs_f64().max(f64::EPSILON);
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_sec).min(capacity);
+        self.updated_at = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / refill_per_sec)
+        }
+    }
+}
+
+/// [`RateLimitGroup`] ごとのトークンバケット。
+///
+/// 設定されていないグループは制限しない。
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: HashMap<RateLimitGroup, Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: impl IntoIterator<Item = (RateLimitGroup, RateLimit)>) -> Self {
+        Self {
+            buckets: limits
+                .into_iter()
+                .map(|(group, limit)| (group, Mutex::new(TokenBucket::new(limit))))
+                .collect(),
+        }
+    }
+
+    pub fn get_limit(&self, group: RateLimitGroup) -> Option<RateLimit> {
+        self.buckets
+            .get(&group)
+            .map(|bucket| bucket.lock().unwrap_or_else(|e| e.into_inner()).limit)
+    }
+
+    /// トークンを 1 個取得する。足りなければ補充されるまで待つ。
+    pub async fn acquire(&self, group: RateLimitGroup) {
+        let Some(bucket) = self.buckets.get(&group) else {
+            return;
+        };
+        // ロックは await をまたがない
+        let wait = bucket
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .reserve(Instant::now());
+        if !wait.is_zero() {
+            tracing::debug!("rate limit {group}: wait {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_group_from_path() {
+        assert_eq!(
+            RateLimitGroup::from_path("/v2/bot/message/push"),
+            RateLimitGroup::Push
+        );
+        assert_eq!(
+            RateLimitGroup::from_path("/v2/bot/message/multicast"),
+            RateLimitGroup::Multicast
+        );
+        assert_eq!(
+            RateLimitGroup::from_path("/v2/bot/message/broadcast"),
+            RateLimitGroup::Broadcast
+        );
+        assert_eq!(
+            RateLimitGroup::from_path("/v2/bot/message/narrowcast"),
+            RateLimitGroup::Narrowcast
+        );
+        assert_eq!(
+            RateLimitGroup::from_path("/v2/bot/message/progress/narrowcast"),
+            RateLimitGroup::Other
+        );
+        assert_eq!(
+            RateLimitGroup::from_path("/proxy/v2/bot/message/push/"),
+            RateLimitGroup::Push
+        );
+        assert_eq!(
+            RateLimitGroup::from_path("/v2/bot/message/reply"),
+            RateLimitGroup::Other
+        );
+    }
+
+    #[test]
+    fn test_token_bucket_reserve() {
+        let mut bucket = TokenBucket::new(RateLimit::per_second(2));
+        let now = bucket.updated_at;
+        // 満タンの 2 個は待たずに使える
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        // 3 個目は 0.5 秒、4 個目は 1 秒待つ(先に予約した分の後ろに並ぶ)
+        assert_eq!(bucket.reserve(now), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(now), Duration::from_secs(1));
+        // 2 秒経てば 4 個補充されて予約分を取り戻し、残り 1 個で待たずに使える
+        assert_eq!(bucket.reserve(now + Duration::from_secs(2)), Duration::ZERO);
+        // 上限を超えては貯まらない
+        let later = now + Duration::from_secs(60);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.reserve(later), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_acquire_waits() {
+        let limiter = RateLimiter::new([(RateLimitGroup::Push, RateLimit::per_second(20))]);
+        let start = Instant::now();
+        for _ in 0..22 {
+            limiter.acquire(RateLimitGroup::Push).await;
+        }
+        // 21, 22 個目でそれぞれ 50ms 待つ
+        assert!(start.elapsed() >= Duration::from_millis(90));
+        // 設定の無いグループは待たない
+        let start = Instant::now();
+        for _ in 0..100 {
+            limiter.acquire(RateLimitGroup::Other).await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(50));
+        assert_eq!(limiter.get_limit(RateLimitGroup::Other), None);
+    }
+}
@@ -0,0 +1,269 @@
+//! リトライ方針。
+//!
+//! `execute_api` は試行が失敗するたびに [`LineOptions`](crate::LineOptions) の
+//! [`RetryPolicy`] へ [`RetryContext`](何回目の試行か・エラー・レスポンスヘッダー・経過時間)
+//! を渡し、返った [`RetryDecision`] に従って待ってからリトライするか、そこで止める。
+//!
+//! [`LineOptionsBuilder::with_retry_policy`](crate::LineOptionsBuilder::with_retry_policy)
+//! を設定しない場合は、従来の `try_count` / `retry_duration` から組み立てた
+//! [`BackoffPolicy::exponential`] が使われる。
+
+use std::time::Duration;
+
+use rand::{RngExt, rngs::StdRng};
+use reqwest::header::HeaderMap;
+
+use crate::error::Error;
+
+/// [`RetryPolicy`] に渡される、失敗した試行の情報。
+#[derive(Debug)]
+pub struct RetryContext<'a> {
+    /// 失敗した試行が何回目か(1 始まり)
+    pub attempt: u32,
+    pub error: &'a Error,
+    /// 失敗したレスポンスのヘッダー(レスポンスを受け取れなかった場合は空)
+    pub headers: &'a HeaderMap,
+    /// 最初の試行を始めてからの経過時間
+    pub elapsed: Duration,
+    /// 直前のリトライで待った時間(初回は `Duration::ZERO`)
+    pub previous_delay: Duration,
+    /// `Retry-After` ヘッダーの値(`max_retry_after` で切り詰め済み、無視する設定なら `None`)
+    pub retry_after: Option<Duration>,
+    /// 同じリクエストを繰り返しても結果が変わらないか(GET/PUT/DELETE、またはリトライキー付き)
+    pub idempotent: bool,
+}
+
+impl RetryContext<'_> {
+    /// 標準の判定でリトライしてよいエラーか([`Error::is_retryable`])。
+    ///
+    /// 5xx と 429(月間上限を除く)、および一時的な通信エラー(接続失敗・タイムアウト・受信失敗)を
+    /// リトライ対象とする。リクエストの組み立て失敗などはリトライしない。
+    ///
+    /// なお冪等でないリクエスト(リトライキーの無い POST)が LINE に届いた可能性がある場合は、
+    /// 方針の判断によらず `execute_api` がリトライしない(二重送信を防ぐため)。
+    pub fn is_retryable(&self) -> bool {
+        self.error.is_retryable()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// 指定時間待ってからリトライする
+    Retry(Duration),
+    /// リトライせずにこのエラーを返す
+    Stop,
+}
+
+/// リトライするかどうかと待ち時間を決める。
+pub trait RetryPolicy: std::fmt::Debug + Send + Sync {
+    fn next(&self, context: &RetryContext) -> RetryDecision;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffKind {
+    /// `base * 2^(n-1)` に `0..jitter` の揺らぎを加える
+    Exponential { jitter: Duration },
+    /// `0..base * 2^(n-1)` から一様に選ぶ
+    FullJitter,
+    /// `base..前回の待ち時間 * 3` から一様に選ぶ
+    DecorrelatedJitter,
+}
+
+/// 組み込みのバックオフ方針。
+///
+/// [`RetryContext::is_retryable`] なエラーを `max_attempts` 回の試行まで繰り返す。
+/// `Retry-After` があればバックオフの計算より優先する。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    pub kind: BackoffKind,
+    /// 最初の試行を含む最大試行回数
+    pub max_attempts: u32,
+    pub base: Duration,
+    /// 1 回あたりの待ち時間の上限
+    pub max_delay: Option<Duration>,
+    /// 最初の試行からの経過時間の上限(待つとこれを超える場合はリトライしない)
+    pub max_elapsed: Option<Duration>,
+}
+
+impl BackoffPolicy {
+    fn new(kind: BackoffKind, max_attempts: u32, base: Duration) -> Self {
+        Self {
+            kind,
+            max_attempts,
+            base,
+            max_delay: None,
+            max_elapsed: None,
+        }
+    }
+
+    /// 指数バックオフ(0〜100ms の揺らぎ付き)。`base` が 0 なら待たずにリトライする。
+    pub fn exponential(max_attempts: u32, base: Duration) -> Self {
+        Self::new(
+            BackoffKind::Exponential {
+                jitter: Duration::from_millis(100),
+            },
+            max_attempts,
+            base,
+        )
+    }
+
+    pub fn full_jitter(max_attempts: u32, base: Duration) -> Self {
+        Self::new(BackoffKind::FullJitter, max_attempts, base)
+    }
+
+    pub fn decorrelated_jitter(max_attempts: u32, base: Duration) -> Self {
+        Self::new(BackoffKind::DecorrelatedJitter, max_attempts, base)
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// `attempt` 回目の試行が失敗したあとの待ち時間を計算する。
+    pub fn delay(&self, attempt: u32, previous_delay: Duration) -> Duration {
+        if self.base.is_zero() {
+            return Duration::ZERO;
+        }
+        let mut rng: StdRng = rand::make_rng();
+        // 1 回目の失敗で base、2 回目で base * 2、3 回目で base * 4
+        let exponential = self
+            .base
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let delay = match self.kind {
+            BackoffKind::Exponential { jitter } => {
+                exponential + random_duration(&mut rng, Duration::ZERO, jitter)
+            }
+            BackoffKind::FullJitter => random_duration(&mut rng, Duration::ZERO, exponential),
+            BackoffKind::DecorrelatedJitter => {
+                let upper = previous_delay.saturating_mul(3).max(self.base);
+                random_duration(&mut rng, self.base, upper)
+            }
+        };
+        match self.max_delay {
+            Some(max_delay) => delay.min(max_delay),
+            None => delay,
+        }
+    }
+}
+
+impl RetryPolicy for BackoffPolicy {
+    fn next(&self, context: &RetryContext) -> RetryDecision {
+        if !context.is_retryable() || context.attempt >= self.max_attempts {
+            return RetryDecision::Stop;
+        }
+        let delay = context
+            .retry_after
+            .unwrap_or_else(|| self.delay(context.attempt, context.previous_delay));
+        if let Some(max_elapsed) = self.max_elapsed
+            && context.elapsed + delay > max_elapsed
+        {
+            return RetryDecision::Stop;
+        }
+        RetryDecision::Retry(delay)
+    }
+}
+
+// [lower, upper) から一様に選ぶ(幅が無ければ lower)
+fn random_duration(rng: &mut StdRng, lower: Duration, upper: Duration) -> Duration {
+    let lower_nanos = lower.as_nanos() as u64;
+    let upper_nanos = upper.as_nanos().min(u64::MAX as u128) as u64;
+    if upper_nanos <= lower_nanos {
+        return lower;
+    }
+    Duration::from_nanos(rng.random_range(lower_nanos..upper_nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_context<'a>(
+        error: &'a Error,
+        headers: &'a HeaderMap,
+        attempt: u32,
+    ) -> RetryContext<'a> {
+        RetryContext {
+            attempt,
+            error,
+            headers,
+            elapsed: Duration::ZERO,
+            previous_delay: Duration::ZERO,
+            retry_after: None,
+            idempotent: true,
+        }
+    }
+
+    fn make_error(status_code: u16) -> Error {
+        Error::OtherText(
+            String::new(),
+            reqwest::StatusCode::from_u16(status_code).unwrap(),
+            crate::LineResponseHeader {
+                request_id: String::new(),
+                accepted_request_id: None,
+                retry_after: None,
+                retry_key: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_backoff_policy_stops() {
+        let headers = HeaderMap::new();
+        let policy = BackoffPolicy::exponential(3, Duration::ZERO);
+        let error = make_error(500);
+        assert_eq!(
+            policy.next(&make_context(&error, &headers, 1)),
+            RetryDecision::Retry(Duration::ZERO)
+        );
+        // 最大試行回数に達した
+        assert_eq!(
+            policy.next(&make_context(&error, &headers, 3)),
+            RetryDecision::Stop
+        );
+        // 4xx はリトライしない
+        let error = make_error(400);
+        assert_eq!(
+            policy.next(&make_context(&error, &headers, 1)),
+            RetryDecision::Stop
+        );
+        // 経過時間の上限を超える
+        let policy = BackoffPolicy::exponential(3, Duration::from_secs(1))
+            .with_max_elapsed(Duration::from_millis(1500));
+        let error = make_error(503);
+        let mut context = make_context(&error, &headers, 1);
+        context.elapsed = Duration::from_millis(600);
+        assert_eq!(policy.next(&context), RetryDecision::Stop);
+        // Retry-After があればバックオフより優先する
+        context.retry_after = Some(Duration::from_millis(100));
+        assert_eq!(
+            policy.next(&context),
+            RetryDecision::Retry(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn test_backoff_policy_delay() {
+        let base = Duration::from_millis(100);
+        for attempt in 1..=4 {
+            let exponential = base * 2u32.pow(attempt - 1);
+            let delay = BackoffPolicy::exponential(5, base).delay(attempt, Duration::ZERO);
+            assert!(delay >= exponential && delay < exponential + Duration::from_millis(100));
+
+            let delay = BackoffPolicy::full_jitter(5, base).delay(attempt, Duration::ZERO);
+            assert!(delay < exponential.max(Duration::from_nanos(1)));
+        }
+        let policy = BackoffPolicy::decorrelated_jitter(5, base);
+        let delay = policy.delay(2, Duration::from_millis(400));
+        assert!(delay >= base && delay < Duration::from_millis(1200));
+        assert_eq!(policy.delay(1, Duration::ZERO), base);
+
+        let policy = BackoffPolicy::full_jitter(5, base).with_max_delay(Duration::from_millis(50));
+        assert!(policy.delay(10, Duration::ZERO) <= Duration::from_millis(50));
+    }
+}
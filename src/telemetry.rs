@@ -0,0 +1,104 @@
+//! `execute_api` の tracing スパン。
+//!
+//! API 呼び出し 1 回につき `line_api` スパン、その中の試行ごとに `line_api.attempt` スパンを作る。
+//! 属性名は OpenTelemetry のセマンティック規約(`http.request.method`・`http.route`・`url.path`・
+//! `http.response.status_code` など)に合わせてあり、`tracing-opentelemetry` 経由でそのまま
+//! コレクターへ送れる。スパン名は `otel.name` でエンドポイント名(`post_v2_bot_message_push` など)
+//! に置き換わる。
+//!
+//! エンドポイント名・メソッド・パスのテンプレートは各エンドポイントが渡す [`Endpoint`] から
+//! 記録する。`url.path` / `url.query` は実際のリクエストから取るが、multipart のように
+//! 複製できないリクエストでは `url.path` をテンプレートで代用する(ベース URL のパスは含まない)。
+//!
+//! `Authorization` ヘッダーとボディは記録しない。クエリ文字列は
+//! [`LineOptions::get_redacted_body_keys`] のキー(`access_token` など)をマスクしてから記録する。
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use tracing::{Span, field::Empty};
+
+use crate::{Endpoint, LineOptions, LineResponseHeader, error::Error, option::redact_query};
+
+/// API 呼び出し全体のスパン。パスとクエリは最初の試行で [`record_request`] する。
+pub(crate) fn request_span(endpoint: &Endpoint) -> Span {
+    tracing::info_span!(
+        "line_api",
+        otel.name = endpoint.name,
+        otel.kind = "client",
+        otel.status_code = Empty,
+        line.endpoint = endpoint.name,
+        http.request.method = endpoint.method,
+        http.route = endpoint.route,
+        url.path = Empty,
+        url.query = Empty,
+        line.attempts = Empty,
+        http.response.status_code = Empty,
+        line.request_id = Empty,
+        line.accepted_request_id = Empty,
+        error.type = Empty,
+        latency_ms = Empty,
+    )
+}
+
+pub(crate) fn attempt_span(attempt: u32) -> Span {
+    tracing::debug_span!(
+        "line_api.attempt",
+        line.attempt = attempt,
+        http.response.status_code = Empty,
+        line.request_id = Empty,
+        line.accepted_request_id = Empty,
+        error.type = Empty,
+        latency_ms = Empty,
+    )
+}
+
+/// 実際のリクエストのパスとマスク済みクエリを記録する。
+/// `request` が `None`(複製できない)ならパスのテンプレートで代用する。
+pub(crate) fn record_request(
+    span: &Span,
+    endpoint: &Endpoint,
+    request: Option<&reqwest::Request>,
+    options: &LineOptions,
+) {
+    let Some(request) = request else {
+        // パスパラメーターを含むものは実際のパスにならないので記録しない
+        if !endpoint.route.contains('{') {
+            span.record("url.path", endpoint.route);
+        }
+        return;
+    };
+    span.record("url.path", request.url().path());
+    if let Some(query) = request.url().query() {
+        let query = redact_query(query, options.get_redacted_body_keys());
+        span.record("url.query", query.as_str());
+    }
+}
+
+/// 成否にかかわらず、受け取ったレスポンスの情報と所要時間を記録する。
+pub(crate) fn record_result<T>(
+    span: &Span,
+    result: &Result<(T, LineResponseHeader, StatusCode), Box<Error>>,
+    latency: Duration,
+) {
+    let (status_code, line_header) = match result {
+        Ok((_, line_header, status_code)) => (Some(*status_code), Some(line_header)),
+        Err(err) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error.type", tracing::field::display(err.kind()));
+            (err.status_code(), err.line_header())
+        }
+    };
+    if let Some(status_code) = status_code {
+        span.record("http.response.status_code", status_code.as_u16());
+    }
+    if let Some(line_header) = line_header {
+        if !line_header.request_id.is_empty() {
+            span.record("line.request_id", line_header.request_id.as_str());
+        }
+        if let Some(accepted_request_id) = &line_header.accepted_request_id {
+            span.record("line.accepted_request_id", accepted_request_id.as_str());
+        }
+    }
+    span.record("latency_ms", latency.as_millis() as u64);
+}